@@ -35,7 +35,7 @@ impl<'a, T: Tile> AnimationPlayer<'a, T> {
         self.frames.get(self.get_reel().index).unwrap()
     }
 
-    fn get_reel(&self) -> &AnimationReel {
+    fn get_reel(&self) -> &AnimationReel<'_> {
         self.reels.get(self.index).unwrap()
     }
 
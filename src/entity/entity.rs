@@ -1,6 +1,24 @@
-use crate::{graphics::tile::Tile, tools::transform::Transform};
+use crate::{
+    graphics::{palette::Palette, tile::Tile},
+    tools::{blend::BlendMode, transform::Transform},
+};
 
 pub trait Entity {
     fn get_position_matrix(&self) -> (&Transform, &impl Tile);
     fn get_order(&self) -> &usize;
+
+    /// how the entity is combined with what is drawn below it
+    fn get_blend_mode(&self) -> BlendMode {
+        BlendMode::NORMAL
+    }
+
+    /// between 0 for invisible and 1 for opaque
+    fn get_opacity(&self) -> f32 {
+        1.
+    }
+
+    /// if set, tiles with palette indices are drawn through it instead of their own colors
+    fn get_palette(&self) -> Option<&Palette> {
+        None
+    }
 }
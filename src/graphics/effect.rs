@@ -0,0 +1,293 @@
+use crate::tools::{
+    blend::BlendMode,
+    color::{Color, Rgba},
+    convolution::EdgeMode,
+    dual_trait::Algebra,
+    matrix::Matrix,
+    parallel,
+    transform::{Dimensions, Position},
+};
+
+/// Effect changes a finished frame before it is shown. Implement it for effects of your own.
+pub trait Effect {
+    fn apply(&mut self, frame: &mut Matrix<Color>);
+}
+
+/// Effect in the chain of a WindowController, which can be turned off without removing it.
+pub struct PostEffect {
+    pub effect: Box<dyn Effect>,
+    pub enabled: bool,
+}
+
+impl PostEffect {
+    /// enabled effect
+    pub fn new(effect: impl Effect + 'static) -> Self {
+        Self {
+            effect: Box::new(effect),
+            enabled: true,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled
+    }
+}
+
+/// multiplies each channel by factor
+fn scale(color: Color, factor: f32) -> Color {
+    let channel = |c: u8| (c as f32 * factor).round().clamp(0., 255.) as u8;
+    Color::new(
+        channel(color.red),
+        channel(color.green),
+        channel(color.blue),
+    )
+}
+
+fn luma(color: Color) -> f32 {
+    0.299 * color.red as f32 + 0.587 * color.green as f32 + 0.114 * color.blue as f32
+}
+
+/// mixes amount from 0 to 1 of to into from
+fn mix(from: Color, to: Color, amount: f32) -> Color {
+    from.lerp(to, amount.clamp(0., 1.))
+}
+
+/// changes each color by func of its position, rows in parallel with the rayon feature
+fn for_each(frame: &mut Matrix<Color>, func: impl Fn(Position, Color) -> Color + Sync + Send) {
+    parallel::for_each_chunk_mut(&mut frame.values, frame.dimensions.width, |y, row| {
+        row.iter_mut()
+            .enumerate()
+            .for_each(|(x, color)| *color = func(Position::new(x as isize, y as isize), *color))
+    })
+}
+
+/// Darkens every spacing-th row like the gaps between the lines of a CRT.
+#[derive(Clone, Copy, Debug)]
+pub struct Scanlines {
+    pub spacing: usize,
+    /// from 0 for no darkening to 1 for black lines
+    pub intensity: f32,
+}
+
+impl Default for Scanlines {
+    fn default() -> Self {
+        Self {
+            spacing: 2,
+            intensity: 0.4,
+        }
+    }
+}
+
+impl Effect for Scanlines {
+    fn apply(&mut self, frame: &mut Matrix<Color>) {
+        let (spacing, factor) = (self.spacing.max(1), 1. - self.intensity.clamp(0., 1.));
+        for_each(frame, |position, color| {
+            if position.y as usize % spacing == spacing - 1 {
+                scale(color, factor)
+            } else {
+                color
+            }
+        })
+    }
+}
+
+/// Darkens towards the corners.
+#[derive(Clone, Copy, Debug)]
+pub struct Vignette {
+    /// from 0 for no darkening to 1 for black corners
+    pub strength: f32,
+    /// distance from the center where darkening starts, 0 at the center and 1 at the corners
+    pub radius: f32,
+}
+
+impl Default for Vignette {
+    fn default() -> Self {
+        Self {
+            strength: 0.6,
+            radius: 0.5,
+        }
+    }
+}
+
+impl Effect for Vignette {
+    fn apply(&mut self, frame: &mut Matrix<Color>) {
+        let (width, height) = (
+            frame.dimensions.width as f32,
+            frame.dimensions.height as f32,
+        );
+        let (strength, radius) = (self.strength.clamp(0., 1.), self.radius.clamp(0., 1.));
+        for_each(frame, |position, color| {
+            let x = (position.x as f32 + 0.5) / width * 2. - 1.;
+            let y = (position.y as f32 + 0.5) / height * 2. - 1.;
+            let distance = ((x * x + y * y) / 2.).sqrt();
+            let t = ((distance - radius) / (1. - radius).max(f32::EPSILON)).clamp(0., 1.);
+            scale(color, 1. - strength * t * t * (3. - 2. * t))
+        })
+    }
+}
+
+/// Removes color.
+#[derive(Clone, Copy, Debug)]
+pub struct Grayscale {
+    /// from 0 for the original colors to 1 for fully gray
+    pub amount: f32,
+}
+
+impl Default for Grayscale {
+    fn default() -> Self {
+        Self { amount: 1. }
+    }
+}
+
+impl Effect for Grayscale {
+    fn apply(&mut self, frame: &mut Matrix<Color>) {
+        let amount = self.amount;
+        for_each(frame, |_, color| {
+            let gray = luma(color).round() as u8;
+            mix(color, Color::new(gray, gray, gray), amount)
+        })
+    }
+}
+
+/// Tints brown like an old photograph.
+#[derive(Clone, Copy, Debug)]
+pub struct Sepia {
+    /// from 0 for the original colors to 1 for fully sepia
+    pub amount: f32,
+}
+
+impl Default for Sepia {
+    fn default() -> Self {
+        Self { amount: 1. }
+    }
+}
+
+impl Effect for Sepia {
+    fn apply(&mut self, frame: &mut Matrix<Color>) {
+        let amount = self.amount;
+        for_each(frame, |_, color| {
+            let [red, green, blue] = [color.red, color.green, color.blue].map(|c| c as f32);
+            let channel = |r: f32, g: f32, b: f32| {
+                (red * r + green * g + blue * b).round().clamp(0., 255.) as u8
+            };
+            let sepia = Color::new(
+                channel(0.393, 0.769, 0.189),
+                channel(0.349, 0.686, 0.168),
+                channel(0.272, 0.534, 0.131),
+            );
+            mix(color, sepia, amount)
+        })
+    }
+}
+
+/// Shifts red and blue apart horizontally like a cheap lens.
+#[derive(Clone, Copy, Debug)]
+pub struct ChromaticAberration {
+    /// distance red is shifted left and blue is shifted right
+    pub offset: isize,
+}
+
+impl Default for ChromaticAberration {
+    fn default() -> Self {
+        Self { offset: 1 }
+    }
+}
+
+impl Effect for ChromaticAberration {
+    fn apply(&mut self, frame: &mut Matrix<Color>) {
+        let source = frame.clone();
+        let last = source.dimensions.width as isize - 1;
+        let at = |x: isize, y: isize| *source.get_wrap(Position::new(x.clamp(0, last), y));
+        frame.fill_with(|Position { x, y }| Color {
+            red: at(x + self.offset, y).red,
+            green: at(x, y).green,
+            blue: at(x - self.offset, y).blue,
+        })
+    }
+}
+
+/// Makes bright areas glow onto their surroundings.
+#[derive(Clone, Copy, Debug)]
+pub struct Bloom {
+    /// brightness from 0 to 255 above which colors glow
+    pub threshold: u8,
+    /// how far the glow spreads, as the standard deviation of a gaussian blur
+    pub sigma: f32,
+    /// from 0 for no glow to 1 for full glow
+    pub intensity: f32,
+}
+
+impl Default for Bloom {
+    fn default() -> Self {
+        Self {
+            threshold: 192,
+            sigma: 2.,
+            intensity: 0.6,
+        }
+    }
+}
+
+impl Effect for Bloom {
+    fn apply(&mut self, frame: &mut Matrix<Color>) {
+        let threshold = self.threshold as f32;
+        let glow = frame
+            .map(|color| {
+                if luma(*color) > threshold {
+                    *color
+                } else {
+                    Color::BLACK
+                }
+            })
+            .gaussian_blur(self.sigma, EdgeMode::TRANSPARENT);
+        let alpha = (self.intensity.clamp(0., 1.) * 255.).round() as u8;
+        frame.blend_overlay(
+            &glow.map(|color| Rgba::new(*color, alpha)),
+            Position::new(0, 0),
+            BlendMode::ADDITIVE,
+        )
+    }
+}
+
+/// Averages blocks of size by size into one color.
+#[derive(Clone, Copy, Debug)]
+pub struct Pixelate {
+    pub size: usize,
+}
+
+impl Default for Pixelate {
+    fn default() -> Self {
+        Self { size: 4 }
+    }
+}
+
+impl Effect for Pixelate {
+    fn apply(&mut self, frame: &mut Matrix<Color>) {
+        let size = self.size.max(1);
+        if size == 1 {
+            return;
+        }
+        let columns = frame.dimensions.width.div_ceil(size);
+        let averages = (0..frame.dimensions.height.div_ceil(size))
+            .flat_map(|y| (0..columns).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let block = frame.view(
+                    Position::new((x * size) as isize, (y * size) as isize),
+                    Dimensions::splat(size),
+                );
+                let count = block.dimensions().area().max(1) as u32;
+                let sum = block.iter().fold([0; 3], |[r, g, b], color| {
+                    [
+                        r + color.red as u32,
+                        g + color.green as u32,
+                        b + color.blue as u32,
+                    ]
+                });
+                let [red, green, blue] = sum.map(|c| ((c + count / 2) / count) as u8);
+                Color::new(red, green, blue)
+            })
+            .collect::<Vec<_>>();
+        frame.fill_with(|position| {
+            averages[position.x as usize / size + position.y as usize / size * columns]
+        })
+    }
+}
@@ -0,0 +1,163 @@
+use crate::tools::{
+    color::Color,
+    dual_trait::Algebra,
+    grid::Grid,
+    matrix::Matrix,
+    transform::{Dimensions, Position},
+};
+
+use super::{map::TileMap, tile::Tile};
+
+/// How light fades between its position and its radius.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Falloff {
+    /// full brightness up to the radius
+    CONSTANT,
+    LINEAR,
+    /// bright near the light and dim towards the radius
+    #[default]
+    QUADRATIC,
+}
+
+impl Falloff {
+    /// brightness from 0 to 1 at distance from a light with radius
+    pub fn at(&self, distance: f32, radius: f32) -> f32 {
+        if distance >= radius {
+            return 0.;
+        }
+        let t = 1. - distance / radius;
+        match self {
+            Self::CONSTANT => 1.,
+            Self::LINEAR => t,
+            Self::QUADRATIC => t * t,
+        }
+    }
+}
+
+/// Light shining in every direction from a point, blocked by solid tiles.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Light {
+    /// position in pixels of the TileMap buffer
+    pub position: Position,
+    /// distance in pixels the light reaches
+    pub radius: f32,
+    pub color: Color,
+    /// multiplies color, above 1 to brighten past the color itself
+    pub intensity: f32,
+    pub falloff: Falloff,
+}
+
+impl Light {
+    pub fn new(position: Position, radius: f32, color: Color) -> Self {
+        Self {
+            position,
+            radius,
+            color,
+            intensity: 1.,
+            falloff: Falloff::default(),
+        }
+    }
+}
+
+/// Lighting of a TileMap: ambient light everywhere plus point lights. Solid tiles, as told by
+/// Tile::is_solid, cast hard shadows a tile at a time.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lighting {
+    /// light reaching everywhere, black for full darkness
+    pub ambient: Color,
+    pub lights: Vec<Light>,
+}
+
+impl Lighting {
+    pub fn new(ambient: Color) -> Self {
+        Self {
+            ambient,
+            lights: Vec::new(),
+        }
+    }
+
+    /// Returns the light reaching each pixel of the buffer of map. Colors are multiplied by it
+    /// when composited, so white leaves them as they are and black hides them.
+    pub fn light_map<T: Tile, M: Grid<Option<T>>>(&self, map: &TileMap<T, M>) -> Matrix<Color> {
+        let tile_dimensions = map.tile_dimensions();
+        let dimensions = map.buffer.dimensions;
+        let tiles = Dimensions::new(
+            dimensions.width.div_ceil(tile_dimensions.width.max(1)),
+            dimensions.height.div_ceil(tile_dimensions.height.max(1)),
+        );
+        let tile_of = |position: Position| {
+            Position::new(
+                position.x.div_euclid(tile_dimensions.width.max(1) as isize),
+                position
+                    .y
+                    .div_euclid(tile_dimensions.height.max(1) as isize),
+            )
+        };
+        let solid = |tile: Position| {
+            map.map
+                .get(map.offset.add(tile))
+                .is_some_and(|t| t.as_ref().is_some_and(Tile::is_solid))
+        };
+        // which tiles of the buffer each light reaches
+        let visible = self
+            .lights
+            .iter()
+            .map(|light| {
+                let from = tile_of(light.position);
+                Matrix::from_fn(tiles, false, |tile| !blocked(from, tile, solid))
+            })
+            .collect::<Vec<_>>();
+        let ambient = [self.ambient.red, self.ambient.green, self.ambient.blue].map(|c| c as f32);
+        let mut light_map = Matrix::new(dimensions, false);
+        light_map.fill_with(|position| {
+            let tile = tile_of(position);
+            let [red, green, blue] = self.lights.iter().zip(&visible).fold(
+                ambient,
+                |[red, green, blue], (light, visible)| {
+                    if !visible.get(tile).copied().unwrap_or(false) {
+                        return [red, green, blue];
+                    }
+                    let (x, y) = (
+                        (position.x - light.position.x) as f32,
+                        (position.y - light.position.y) as f32,
+                    );
+                    let brightness =
+                        light.intensity * light.falloff.at((x * x + y * y).sqrt(), light.radius);
+                    [
+                        red + light.color.red as f32 * brightness,
+                        green + light.color.green as f32 * brightness,
+                        blue + light.color.blue as f32 * brightness,
+                    ]
+                },
+            );
+            let channel = |c: f32| c.round().clamp(0., 255.) as u8;
+            Color::new(channel(red), channel(green), channel(blue))
+        });
+        light_map
+    }
+}
+
+/// whether a solid tile lies on the line between tiles from and to, not counting either end
+fn blocked(from: Position, to: Position, solid: impl Fn(Position) -> bool) -> bool {
+    let (dx, dy) = ((to.x - from.x).abs(), -(to.y - from.y).abs());
+    let (sx, sy) = ((to.x - from.x).signum(), (to.y - from.y).signum());
+    let (mut position, mut error) = (from, dx + dy);
+    while position.x != to.x || position.y != to.y {
+        let doubled = error * 2;
+        if doubled >= dy {
+            error += dy;
+            position.x += sx;
+        }
+        if doubled <= dx {
+            error += dx;
+            position.y += sy;
+        }
+        if (position.x != to.x || position.y != to.y) && solid(position) {
+            return true;
+        }
+    }
+    false
+}
@@ -1,41 +1,134 @@
 use crate::tools::{
-    color::Color,
+    color::{Color, Pixel},
     dual_trait::Algebra,
+    grid::Grid,
     matrix::Matrix,
+    parallel,
+    resize::Anchor,
     transform::{Dimensions, Position},
 };
 
-use super::tile::Tile;
+use super::{palette::Palette, tile::Tile};
 
-/// Where each tile is placed
-pub struct TileMap<T: Tile> {
-    /// matrix storing the tiles in the map
-    pub map: Matrix<Option<T>>,
+/// Where each tile is placed. The tiles are stored in a Matrix by default, or in any other Grid
+/// such as a ChunkedMatrix for maps too large to store whole.
+pub struct TileMap<T: Tile, M: Grid<Option<T>> = Matrix<Option<T>>> {
+    /// grid storing the tiles in the map
+    pub map: M,
+    /// position in tiles of the map drawn at the top left of buffer
+    pub offset: Position,
     /// dimensions in pixels of each tile
     tile_dimensions: Dimensions,
     /// color rendition of map
     pub buffer: Matrix<Color>,
+    /// if set, tiles with palette indices are drawn through it. Changing it takes effect on the
+    /// next update_buffer.
+    pub palette: Option<Palette>,
+    /// if set, the buffer is cleared to it before tiles are drawn, so empty positions and
+    /// transparent pixels don't keep what was drawn before, such as after offset changes. If
+    /// None, tiles are drawn over whatever is already in the buffer.
+    pub background: Option<Color>,
+    tile: std::marker::PhantomData<T>,
 }
 
 impl<T: Tile> TileMap<T> {
     pub fn new(dimensions: Dimensions, wrapping: bool, tile_dimensions: Dimensions) -> Self {
         Self {
             map: Matrix::new(dimensions, wrapping),
+            offset: Position::default(),
             tile_dimensions,
             buffer: Matrix::new(dimensions.mul(tile_dimensions), wrapping),
+            palette: None,
+            background: None,
+            tile: std::marker::PhantomData,
         }
     }
 
-    /// updates tilemap buffer. must be done at least once to have tilemap display
+    /// Changes the dimensions in tiles of the map, keeping tiles placed by anchor. The buffer is
+    /// resized and redrawn to match.
+    pub fn resize(&mut self, dimensions: Dimensions, anchor: Anchor) {
+        self.map.resize(dimensions, anchor, None);
+        self.buffer = Matrix::new(dimensions.mul(self.tile_dimensions), self.map.wrapping);
+        self.update_buffer()
+    }
+}
+
+impl<T: Tile, M: Grid<Option<T>>> TileMap<T, M> {
+    /// TileMap backed by given grid. dimensions are the number of tiles drawn to buffer.
+    pub fn with_map(map: M, dimensions: Dimensions, tile_dimensions: Dimensions) -> Self {
+        Self {
+            map,
+            offset: Position::default(),
+            tile_dimensions,
+            buffer: Matrix::new(dimensions.mul(tile_dimensions), false),
+            palette: None,
+            background: None,
+            tile: std::marker::PhantomData,
+        }
+    }
+
+    /// dimensions in pixels of each tile
+    pub fn tile_dimensions(&self) -> Dimensions {
+        self.tile_dimensions
+    }
+
+    /// updates tilemap buffer. must be done at least once to have tilemap display.
+    /// Tiles are drawn over the buffer, which is first cleared to background if it is set.
+    /// Each row of tiles is drawn in parallel with the rayon feature.
     pub fn update_buffer(&mut self) {
-        self.map.enumerate().for_each(|(position, u)| {
-            if let Some(tile) = u {
-                self.buffer.transparent_overlay_iter(
-                    tile.get_iter(),
-                    position.mul(self.tile_dimensions.into_dual::<Position>()),
-                    self.tile_dimensions,
-                )
+        let tile_dimensions = self.tile_dimensions;
+        let width = self.buffer.dimensions.width;
+        let (map, offset, palette, background) = (
+            &self.map,
+            self.offset,
+            self.palette.as_ref(),
+            self.background,
+        );
+        parallel::for_each_chunk_mut(
+            &mut self.buffer.values,
+            width * tile_dimensions.height,
+            |y, band| {
+                if let Some(background) = background {
+                    band.fill(background)
+                }
+                (0..width / tile_dimensions.width.max(1)).for_each(|x| {
+                    if let Some(Some(tile)) =
+                        map.get(offset.add(Position::new(x as isize, y as isize)))
+                    {
+                        match (palette, tile.get_indices()) {
+                            (Some(palette), Some(indices)) => draw_tile(
+                                band,
+                                palette.resolve(indices.values.iter()),
+                                x,
+                                tile_dimensions,
+                                width,
+                            ),
+                            _ => draw_tile(band, tile.get_iter(), x, tile_dimensions, width),
+                        }
+                    }
+                })
+            },
+        )
+    }
+}
+
+/// draws pixels of the tile in column x of a band of tiles with width in pixels
+fn draw_tile(
+    band: &mut [Color],
+    pixels: impl Iterator<Item = Pixel>,
+    x: usize,
+    tile_dimensions: Dimensions,
+    width: usize,
+) {
+    pixels
+        .take(tile_dimensions.area())
+        .enumerate()
+        .for_each(|(i, pixel)| {
+            let index = x * tile_dimensions.width
+                + i % tile_dimensions.width
+                + i / tile_dimensions.width * width;
+            if let (Some(color), Some(t)) = (pixel, band.get_mut(index)) {
+                *t = color
             }
         })
-    }
 }
@@ -0,0 +1,114 @@
+use crate::tools::{
+    color::{Color, Pixel},
+    matrix::Matrix,
+};
+
+use super::tile::Tile;
+
+/// Palette maps color indices to colors. Index 0 is always transparent, so indexed tiles can use
+/// it for their background.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Palette {
+    /// color of each index, starting at index 0
+    colors: Vec<Pixel>,
+}
+
+impl Palette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// palette with colors from index 1 onwards
+    pub fn from_colors(colors: impl IntoIterator<Item = Color>) -> Self {
+        Self {
+            colors: std::iter::once(None)
+                .chain(colors.into_iter().map(Some))
+                .collect::<Vec<_>>(),
+        }
+    }
+
+    /// sets color of index. Does nothing for index 0, which stays transparent.
+    pub fn add(&mut self, index: u8, color: impl Into<Color>) {
+        if index == 0 {
+            return;
+        }
+        let index = index as usize;
+        if self.colors.len() <= index {
+            self.colors.resize(index + 1, None);
+        }
+        self.colors[index] = Some(color.into())
+    }
+
+    /// color of index, None if transparent or never added
+    pub fn get(&self, index: u8) -> Pixel {
+        self.colors.get(index as usize).copied().flatten()
+    }
+
+    /// lists each index that has a color with its color
+    pub fn colors(&self) -> impl Iterator<Item = (u8, Color)> + '_ {
+        self.colors
+            .iter()
+            .enumerate()
+            .filter_map(|(index, pixel)| pixel.map(|color| (index as u8, color)))
+    }
+
+    /// number of indices, including the transparent index 0
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// looks up the color of each index
+    pub fn resolve<'a>(
+        &'a self,
+        indices: impl Iterator<Item = &'a u8> + 'a,
+    ) -> impl Iterator<Item = Pixel> + 'a {
+        indices.map(|index| self.get(*index))
+    }
+
+    /// matrix of the colors of indices
+    pub fn resolve_matrix(&self, indices: &Matrix<u8>) -> Matrix<Pixel> {
+        indices.map(|index| self.get(*index))
+    }
+}
+
+/// Tile stored as palette indices. Its pixels are resolved through the palette it was made with,
+/// while a TileMap or Entity with a palette of its own resolves the indices through that instead.
+#[derive(Clone, Debug)]
+pub struct IndexedTile {
+    indices: Matrix<u8>,
+    /// indices resolved through the palette of the tile
+    pixels: Matrix<Pixel>,
+}
+
+impl IndexedTile {
+    pub fn new(indices: Matrix<u8>, palette: &Palette) -> Self {
+        Self {
+            pixels: palette.resolve_matrix(&indices),
+            indices,
+        }
+    }
+
+    /// resolves the indices through palette instead
+    pub fn set_palette(&mut self, palette: &Palette) {
+        self.pixels = palette.resolve_matrix(&self.indices)
+    }
+}
+
+impl Tile for IndexedTile {
+    fn get_matrix(&self) -> &Matrix<Pixel> {
+        &self.pixels
+    }
+
+    fn get_iter(&self) -> impl Iterator<Item = Pixel> {
+        self.pixels.values.iter().copied()
+    }
+
+    fn get_indices(&self) -> Option<&Matrix<u8>> {
+        Some(&self.indices)
+    }
+}
@@ -1,7 +1,27 @@
-use crate::tools::{color::Pixel, matrix::Matrix};
+use crate::tools::{
+    color::{Pixel, Rgba},
+    matrix::Matrix,
+};
 
 /// Tiles that make up a map or entity
 pub trait Tile: Clone + Sized + Sync + Send {
     fn get_matrix(&self) -> &Matrix<Pixel>;
     fn get_iter(&self) -> impl Iterator<Item = Pixel>;
+
+    /// palette indices of the tile, if it has them. A TileMap or Entity with a palette draws
+    /// these through its palette instead of the pixels of get_matrix.
+    fn get_indices(&self) -> Option<&Matrix<u8>> {
+        None
+    }
+
+    /// colors of the tile with alpha, if it has them. Entities draw these instead of the pixels
+    /// of get_matrix, so each pixel can be partly transparent.
+    fn get_rgba(&self) -> Option<&Matrix<Rgba>> {
+        None
+    }
+
+    /// whether the tile blocks light, casting shadows
+    fn is_solid(&self) -> bool {
+        false
+    }
 }
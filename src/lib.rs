@@ -2,19 +2,41 @@
 //! Mainly, this is for personal use so I will update this semi-infrequently.
 
 pub mod tools {
+    pub mod automaton;
+    pub mod blend;
+    pub mod chunked;
     pub mod color;
+    pub mod color_space;
+    pub mod convolution;
+    pub mod diff;
+    pub mod dither;
+    pub mod draw;
     pub mod dual_trait;
+    pub mod grid;
     pub mod matrix;
+    pub mod named_colors;
+    pub(crate) mod parallel;
+    pub mod pathing;
+    pub mod quantize;
+    pub mod region;
+    pub mod resize;
+    pub mod sampling;
+    pub mod scaling;
     pub mod timer;
     pub mod transform;
+    pub mod view;
 }
 pub mod entity {
     pub mod animation;
+    #[allow(clippy::module_inception)]
     pub mod entity;
 }
 pub mod graphics {
+    pub mod effect;
     pub mod library;
+    pub mod lighting;
     pub mod map;
+    pub mod palette;
     pub mod tile;
 }
 pub mod window;
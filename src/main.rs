@@ -1,12 +1,17 @@
-use minifb::{Key, Scale};
+use minifb::{Key, KeyRepeat, Scale};
 use minifb_tile_base::{
     entity::entity::Entity,
-    graphics::{map::TileMap, tile::Tile},
+    graphics::{
+        lighting::{Light, Lighting},
+        map::TileMap,
+        tile::Tile,
+    },
     tools::{
         color::{Color, Pixel},
         dual_trait::Algebra,
         matrix::Matrix,
-        transform::{Dimensions, Position, Rotation, Transform},
+        scaling::Upscale,
+        transform::{Dimensions, Orientation, Position, Rotation, Transform},
     },
     window::WindowController,
 };
@@ -42,6 +47,7 @@ impl Entity for Player {
 }
 
 #[derive(Clone)]
+#[allow(clippy::upper_case_acronyms)]
 enum TileBase {
     ONE(Matrix<Pixel>),
     TWO(Matrix<Pixel>),
@@ -53,56 +59,32 @@ enum TileBase {
 impl TileBase {
     fn from_usize(i: usize) -> Self {
         match i % 5 {
-            0 => Self::ONE(Matrix {
-                values: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]
-                    .iter()
-                    .map(|u| Some((*u * 16).into()))
-                    .collect::<Vec<_>>(),
-                dimensions: Dimensions::splat(4),
-                wrapping: false,
-            }),
-            1 => Self::TWO(Matrix {
-                values: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]
-                    .iter()
-                    .rev()
-                    .map(|u| Some((*u * 16).into()))
-                    .collect::<Vec<_>>(),
-
-                dimensions: Dimensions::splat(4),
-                wrapping: false,
-            }),
-            2 => Self::THREE(Matrix {
-                values: [0, 2, 1, 3, 4, 6, 5, 7, 8, 10, 9, 11, 12, 14, 13, 15]
-                    .iter()
-                    .map(|u| Some((*u * 16).into()))
-                    .collect::<Vec<_>>(),
-
-                dimensions: Dimensions::splat(4),
-                wrapping: false,
-            }),
-            3 => Self::FOUR(Matrix {
-                values: [0, 2, 1, 3, 4, 6, 5, 7, 8, 10, 9, 11, 12, 14, 13, 15]
-                    .iter()
-                    .rev()
-                    .map(|u| Some((*u * 16).into()))
-                    .collect::<Vec<_>>(),
-
-                dimensions: Dimensions::splat(4),
-                wrapping: false,
-            }),
-            _ => Self::FIVE(Matrix {
-                values: [3, 1, 2, 0, 7, 5, 6, 4, 11, 9, 10, 8, 15, 13, 14, 12]
-                    .iter()
-                    .map(|u| Some((*u * 16).into()))
-                    .collect::<Vec<_>>(),
-
-                dimensions: Dimensions::splat(4),
-                wrapping: false,
-            }),
+            0 => Self::ONE(shades([
+                0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+            ])),
+            1 => Self::TWO(shades([
+                15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0,
+            ])),
+            2 => Self::THREE(shades([
+                0, 2, 1, 3, 4, 6, 5, 7, 8, 10, 9, 11, 12, 14, 13, 15,
+            ])),
+            3 => Self::FOUR(shades([
+                15, 13, 14, 12, 11, 9, 10, 8, 7, 5, 6, 4, 3, 1, 2, 0,
+            ])),
+            _ => Self::FIVE(shades([
+                3, 1, 2, 0, 7, 5, 6, 4, 11, 9, 10, 8, 15, 13, 14, 12,
+            ])),
         }
     }
 }
 
+/// 4x4 tile of grays given row by row
+fn shades(values: [u32; 16]) -> Matrix<Pixel> {
+    Matrix::from_fn(Dimensions::splat(4), false, |position| {
+        Some((values[position.x as usize + position.y as usize * 4] * 16).into())
+    })
+}
+
 impl Tile for TileBase {
     fn get_iter(&self) -> impl Iterator<Item = Pixel> {
         self.get_matrix().values.iter().copied()
@@ -113,6 +95,10 @@ impl Tile for TileBase {
             Self::ONE(m) | Self::TWO(m) | Self::THREE(m) | Self::FOUR(m) | Self::FIVE(m) => m,
         }
     }
+
+    fn is_solid(&self) -> bool {
+        matches!(self, Self::FIVE(_))
+    }
 }
 
 fn main() {
@@ -128,49 +114,39 @@ fn main() {
     );
 
     let mut window_controller = WindowController::new("title", DIMENSIONS, Scale::X4, true);
-    window_controller.matrix.values.fill(500.into());
+    window_controller.matrix.values.fill(Color::new(0, 1, 244));
 
+    let (red, white) = (Some(Color::RED), Some(Color::WHITE));
     let mut player = Player {
         transform: Transform::default(),
-        matrix: Matrix {
-            dimensions: Dimensions::splat(4),
-            values: vec![
-                None,
-                Some(0xFF0000.into()),
-                Some(0xFF0000.into()),
-                None,
-                Some(0xFFFFFF.into()),
-                Some(0xFF0000.into()),
-                Some(0xFF0000.into()),
-                Some(0xFFFFFF.into()),
-                Some(0xFF0000.into()),
-                Some(0xFF0000.into()),
-                Some(0xFF0000.into()),
-                Some(0xFF0000.into()),
-                None,
-                Some(0xFF0000.into()),
-                Some(0xFF0000.into()),
-                None,
+        matrix: Matrix::from_rows(
+            [
+                [None, red, red, None],
+                [white, red, red, white],
+                [red; 4],
+                [None, red, red, None],
             ],
-            wrapping: false,
-        },
+            false,
+        )
+        .expect("rows are the same length"),
     };
 
     let mut map = TileMap::<TileBase>::new(Dimensions::splat(25), false, Dimensions::splat(4));
-    map.map
-        .enumerate_mut()
-        .for_each(|(position, u)| *u = Some(TileBase::from_usize(position.mul_self() % 5)));
-    /*
-          (0..16).for_each(|i| {
-              map.palatte.add(i, 0xF << i);
-              println!("None {}, {:?}", i, map.palatte.get(i))
-          });
-    */
+    map.map.enumerate_mut().for_each(|(position, u)| {
+        *u = Some(TileBase::from_usize(position.mul_self() as usize % 5))
+    });
     map.update_buffer();
     window_controller
         .update_buffer(map.buffer.values.iter().copied())
         .expect("update failed");
 
+    let mut lighting = Lighting::new(Color::new(40, 40, 60));
+    lighting.lights.push(Light::new(
+        player.transform.position,
+        40.,
+        Color::new(255, 200, 120),
+    ));
+
     while window_controller.window.is_open() && !window_controller.window.is_key_down(Key::Escape) {
         window_controller
             .window
@@ -179,23 +155,40 @@ fn main() {
             .for_each(|k| match k {
                 Key::W => {
                     player.transform.position.y -= 1;
-                    player.transform.rotation = Rotation::UP
+                    player.transform.orientation = Rotation::UP.into()
                 }
                 Key::A => {
                     player.transform.position.x -= 1;
-                    player.transform.rotation = Rotation::LEFT
+                    player.transform.orientation =
+                        Orientation::from(Rotation::RIGHT).then(Orientation::REFLECT_HORIZONTAL)
                 }
                 Key::S => {
                     player.transform.position.y += 1;
-                    player.transform.rotation = Rotation::DOWN
+                    player.transform.orientation = Rotation::DOWN.into()
                 }
                 Key::D => {
                     player.transform.position.x += 1;
-                    player.transform.rotation = Rotation::RIGHT
+                    player.transform.orientation = Rotation::RIGHT.into()
                 }
+                Key::Q => player.transform.angle -= 0.05,
+                Key::E => player.transform.angle += 0.05,
                 _ => (),
             });
 
+        // H turns smoothing of the window on and off
+        if window_controller
+            .window
+            .is_key_pressed(Key::H, KeyRepeat::No)
+        {
+            window_controller.upscale = match window_controller.upscale {
+                Upscale::NONE => Upscale::HQ2X,
+                _ => Upscale::NONE,
+            }
+        }
+
+        // the torch follows the center of the player
+        lighting.lights[0].position = player.transform.position.add(Position::splat(2));
+        window_controller.light_map = Some(lighting.light_map(&map));
         window_controller
             .matrix
             .overlay(&map.buffer, Position::splat(0));
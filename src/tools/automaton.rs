@@ -0,0 +1,127 @@
+use std::mem;
+
+use super::{dual_trait::Algebra, matrix::Matrix, transform::Position};
+
+/// Which values around a cell are its neighbours.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Neighbourhood {
+    /// the square of values up to radius away, diagonals included
+    MOORE(usize),
+    /// the diamond of values up to radius steps away, diagonals not included
+    NEUMANN(usize),
+}
+
+impl Default for Neighbourhood {
+    fn default() -> Self {
+        Self::MOORE(1)
+    }
+}
+
+impl Neighbourhood {
+    /// offsets of each neighbour row by row, without the cell itself
+    pub fn offsets(&self) -> Vec<Position> {
+        let (Self::MOORE(radius) | Self::NEUMANN(radius)) = *self;
+        let radius = radius as isize;
+        (-radius..=radius)
+            .flat_map(|y| (-radius..=radius).map(move |x| Position::new(x, y)))
+            .filter(|offset| match self {
+                Self::MOORE(_) => true,
+                Self::NEUMANN(_) => offset.x.abs() + offset.y.abs() <= radius,
+            })
+            .filter(|offset| offset.x != 0 || offset.y != 0)
+            .collect::<Vec<_>>()
+    }
+}
+
+/// The neighbours of a cell in the current generation, given to the rule of an Automaton.
+pub struct Neighbours<'a, T: Clone> {
+    matrix: &'a Matrix<T>,
+    position: Position,
+    offsets: &'a [Position],
+}
+
+impl<'a, T: Clone> Neighbours<'a, T> {
+    /// position of the cell
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// value at offset from the cell, inside the neighbourhood or not. None past the edges of a
+    /// matrix that isn't wrapping.
+    pub fn get(&self, offset: Position) -> Option<&'a T> {
+        self.matrix.get(self.position.add(offset))
+    }
+
+    /// lists the neighbours in the neighbourhood that are in bounds
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.offsets.iter().filter_map(|offset| self.get(*offset))
+    }
+
+    /// number of neighbours where predicate is true
+    pub fn count(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        self.iter().filter(|t| predicate(t)).count()
+    }
+}
+
+/// Automaton steps a matrix through generations of a rule. Each generation is written into a
+/// second matrix which is then swapped in, so rules only ever see the previous generation.
+pub struct Automaton<T: Clone> {
+    current: Matrix<T>,
+    next: Matrix<T>,
+    offsets: Vec<Position>,
+    /// number of steps taken
+    pub generation: usize,
+}
+
+impl<T: Default + Clone + Sync + Send> Automaton<T> {
+    /// Runs on matrix. Edges wrap if the matrix is wrapping.
+    pub fn new(matrix: Matrix<T>, neighbourhood: Neighbourhood) -> Self {
+        Self {
+            next: matrix.clone(),
+            current: matrix,
+            offsets: neighbourhood.offsets(),
+            generation: 0,
+        }
+    }
+
+    /// current generation
+    pub fn matrix(&self) -> &Matrix<T> {
+        &self.current
+    }
+
+    /// current generation, for changes between steps
+    pub fn matrix_mut(&mut self) -> &mut Matrix<T> {
+        &mut self.current
+    }
+
+    pub fn into_matrix(self) -> Matrix<T> {
+        self.current
+    }
+
+    /// Replaces every cell by rule of the cell and its neighbours. Rows are computed in parallel
+    /// with the rayon feature.
+    pub fn step(&mut self, rule: impl Fn(&T, &Neighbours<T>) -> T + Sync + Send) {
+        if self.next.dimensions.area() != self.current.dimensions.area() {
+            self.next = self.current.clone();
+        }
+        self.next.dimensions = self.current.dimensions;
+        self.next.wrapping = self.current.wrapping;
+        let (current, offsets) = (&self.current, &self.offsets);
+        self.next.fill_with(|position| {
+            let neighbours = Neighbours {
+                matrix: current,
+                position,
+                offsets,
+            };
+            rule(current.get_wrap(position), &neighbours)
+        });
+        mem::swap(&mut self.current, &mut self.next);
+        self.generation += 1
+    }
+
+    /// steps given number of times with the same rule
+    pub fn run(&mut self, steps: usize, rule: impl Fn(&T, &Neighbours<T>) -> T + Sync + Send) {
+        (0..steps).for_each(|_| self.step(&rule))
+    }
+}
@@ -0,0 +1,79 @@
+use super::{
+    color::{Color, Rgba},
+    matrix::Matrix,
+    transform::{Dimensions, Position},
+    view::MatrixView,
+};
+
+/// How a color drawn on top is combined with the color below it.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlendMode {
+    /// replaces the color below
+    #[default]
+    NORMAL,
+    /// adds to the color below, brightening it
+    ADDITIVE,
+    /// multiplies with the color below, darkening it
+    MULTIPLY,
+    /// inverse of MULTIPLY, brightening the color below
+    SCREEN,
+    /// MULTIPLY on dark colors and SCREEN on light colors below
+    OVERLAY,
+}
+
+impl BlendMode {
+    /// blends two channels between 0 and 1
+    fn channel(&self, below: f32, above: f32) -> f32 {
+        match self {
+            Self::NORMAL => above,
+            Self::ADDITIVE => (below + above).min(1.),
+            Self::MULTIPLY => below * above,
+            Self::SCREEN => 1. - (1. - below) * (1. - above),
+            Self::OVERLAY if below < 0.5 => 2. * below * above,
+            Self::OVERLAY => 1. - 2. * (1. - below) * (1. - above),
+        }
+    }
+
+    /// Blends above onto below. The result is mixed with below by the alpha of above.
+    pub fn blend(&self, below: Color, above: Rgba) -> Color {
+        let alpha = above.alpha as f32 / 255.;
+        let channel = |below: u8, above: u8| {
+            let below = below as f32 / 255.;
+            let blended = self.channel(below, above as f32 / 255.);
+            ((below + (blended - below) * alpha) * 255.).round() as u8
+        };
+        Color {
+            red: channel(below.red, above.red),
+            green: channel(below.green, above.green),
+            blue: channel(below.blue, above.blue),
+        }
+    }
+}
+
+impl Matrix<Color> {
+    /// Blends matrix or view onto matrix starting at position (x, y), weighted by alpha.
+    pub fn blend_overlay<'a>(
+        &mut self,
+        matrix: impl Into<MatrixView<'a, Rgba>>,
+        position: Position,
+        mode: BlendMode,
+    ) {
+        let view = matrix.into();
+        self.blend_overlay_iter(view.iter().copied(), position, view.dimensions(), mode)
+    }
+
+    pub fn blend_overlay_iter(
+        &mut self,
+        iter: impl Iterator<Item = Rgba>,
+        position: Position,
+        dimensions: Dimensions,
+        mode: BlendMode,
+    ) {
+        self.blit(iter, position, dimensions, |t, u| {
+            if u.alpha > 0 {
+                *t = mode.blend(*t, u)
+            }
+        })
+    }
+}
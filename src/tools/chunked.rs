@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+
+use super::{
+    dual_trait::Algebra,
+    grid::Grid,
+    matrix::Matrix,
+    transform::{Dimensions, Position, Rect},
+};
+
+/// ChunkedMatrix is an unbounded 2D grid stored as chunks of fixed dimensions. Chunks are only
+/// created when written to, so large and sparse worlds stay cheap.
+#[derive(Clone, Debug)]
+pub struct ChunkedMatrix<T: Default + Clone> {
+    /// chunks by their (x, y) in chunks, not values
+    chunks: HashMap<(isize, isize), Matrix<T>>,
+    chunk_dimensions: Dimensions,
+    /// value of every position in a new chunk
+    default: T,
+}
+
+impl<T: Default + Clone + Sync + Send> Default for ChunkedMatrix<T> {
+    fn default() -> Self {
+        Self::with_default(Dimensions::splat(32), T::default())
+    }
+}
+
+impl<T: Default + Clone + Sync + Send> ChunkedMatrix<T> {
+    pub fn new(chunk_dimensions: Dimensions) -> Self {
+        Self::with_default(chunk_dimensions, T::default())
+    }
+
+    /// new chunks are filled with default instead of T::default()
+    pub fn with_default(chunk_dimensions: Dimensions, default: T) -> Self {
+        assert!(
+            chunk_dimensions.area() > 0,
+            "chunk dimensions must not be empty"
+        );
+        Self {
+            chunks: HashMap::new(),
+            chunk_dimensions,
+            default,
+        }
+    }
+
+    pub fn chunk_dimensions(&self) -> Dimensions {
+        self.chunk_dimensions
+    }
+
+    /// number of chunks created so far
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// returns the chunk holding position and the index of position inside it
+    fn locate(&self, position: Position) -> ((isize, isize), usize) {
+        let (width, height) = (
+            self.chunk_dimensions.width as isize,
+            self.chunk_dimensions.height as isize,
+        );
+        (
+            (position.x.div_euclid(width), position.y.div_euclid(height)),
+            (position.x.rem_euclid(width) + position.y.rem_euclid(height) * width) as usize,
+        )
+    }
+
+    /// position of the first value of chunk
+    fn origin(&self, (x, y): (isize, isize)) -> Position {
+        Position::new(
+            x * self.chunk_dimensions.width as isize,
+            y * self.chunk_dimensions.height as isize,
+        )
+    }
+
+    /// returns None if nothing was ever written to the chunk holding position
+    pub fn get(&self, position: Position) -> Option<&T> {
+        let (chunk, index) = self.locate(position);
+        self.chunks.get(&chunk).map(|matrix| &matrix.values[index])
+    }
+
+    /// returns value at position, or the default if its chunk was never written to
+    pub fn get_or_default(&self, position: Position) -> &T {
+        self.get(position).unwrap_or(&self.default)
+    }
+
+    /// creates the chunk holding position if it doesn't exist yet
+    pub fn get_mut(&mut self, position: Position) -> &mut T {
+        let (chunk, index) = self.locate(position);
+        let (dimensions, default) = (self.chunk_dimensions, &self.default);
+        &mut self
+            .chunks
+            .entry(chunk)
+            .or_insert_with(|| Matrix {
+                values: vec![default.clone(); dimensions.area()],
+                dimensions,
+                wrapping: false,
+            })
+            .values[index]
+    }
+
+    pub fn set(&mut self, position: Position, value: T) {
+        *self.get_mut(position) = value
+    }
+
+    /// Applies func to given value and sets it back in the ChunkedMatrix.
+    pub fn apply(&mut self, position: Position, func: impl Fn(&T) -> T) {
+        let value = self.get_mut(position);
+        *value = func(value);
+    }
+
+    /// Lists values of created chunks, chunk by chunk in no particular order.
+    pub fn enumerate(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.chunks.iter().flat_map(|(chunk, matrix)| {
+            let origin = self.origin(*chunk);
+            matrix
+                .enumerate()
+                .map(move |(position, t)| (origin.add(position), t))
+        })
+    }
+
+    /// Lists mutable values of created chunks, chunk by chunk in no particular order.
+    pub fn enumerate_mut(&mut self) -> impl Iterator<Item = (Position, &mut T)> {
+        let dimensions = self.chunk_dimensions;
+        self.chunks.iter_mut().flat_map(move |((x, y), matrix)| {
+            let origin = Position::new(
+                x * dimensions.width as isize,
+                y * dimensions.height as isize,
+            );
+            matrix
+                .enumerate_mut()
+                .map(move |(position, t)| (origin.add(position), t))
+        })
+    }
+
+    /// Lists values with width and height starting at (x, y) row by row. Positions in chunks
+    /// that don't exist give the default.
+    pub fn clamp(&self, position: Position, dimensions: Dimensions) -> impl Iterator<Item = &T> {
+        (0..dimensions.area()).map(move |i| {
+            self.get_or_default(position.add(Position::new(
+                (i % dimensions.width) as isize,
+                (i / dimensions.width) as isize,
+            )))
+        })
+    }
+
+    /// copies the area with width and height starting at (x, y) into a new matrix
+    pub fn clamp_to_matrix(&self, position: Position, dimensions: Dimensions) -> Matrix<T> {
+        Matrix {
+            values: self
+                .clamp(position, dimensions)
+                .cloned()
+                .collect::<Vec<_>>(),
+            dimensions,
+            wrapping: false,
+        }
+    }
+
+    /// smallest area covering every created chunk, None if there are none
+    pub fn bounds(&self) -> Option<Rect> {
+        let mut chunks = self.chunks.keys();
+        let first = *chunks.next()?;
+        let (min, max) = chunks.fold((first, first), |(min, max), &(x, y)| {
+            ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+        });
+        Some(Rect::new(
+            self.origin(min),
+            Dimensions::new(
+                (max.0 - min.0 + 1) as usize * self.chunk_dimensions.width,
+                (max.1 - min.1 + 1) as usize * self.chunk_dimensions.height,
+            ),
+        ))
+    }
+
+    /// drops the chunk holding position, returning its positions to the default
+    pub fn clear_chunk(&mut self, position: Position) {
+        let (chunk, _) = self.locate(position);
+        self.chunks.remove(&chunk);
+    }
+}
+
+impl<T: Default + Clone + Sync + Send> Grid<T> for ChunkedMatrix<T> {
+    fn get(&self, position: Position) -> Option<&T> {
+        ChunkedMatrix::get(self, position)
+    }
+
+    fn set(&mut self, position: Position, value: T) {
+        ChunkedMatrix::set(self, position, value)
+    }
+}
@@ -1,4 +1,13 @@
-#[derive(Debug, Clone, Copy, Default)]
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use super::named_colors;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     pub red: u8,
     pub green: u8,
@@ -7,29 +16,37 @@ pub struct Color {
 
 impl From<u32> for Color {
     fn from(value: u32) -> Self {
-        Color {
-            red: (value >> 16) as u8,
-            green: (value >> 8) as u8,
-            blue: value as u8,
-        }
+        Self::from_hex(value)
     }
 }
 
 impl Color {
-    pub fn distance_from(&self, rhs: Self) -> u8 {
-        return if self.red > rhs.red {
-            self.red - rhs.red
-        } else {
-            rhs.red - self.red
-        } + if self.green > rhs.green {
-            self.green - rhs.green
-        } else {
-            rhs.green - self.green
-        } + if self.blue > rhs.blue {
-            self.blue - rhs.blue
-        } else {
-            rhs.blue - self.blue
-        };
+    pub const BLACK: Self = Self::new(0, 0, 0);
+    pub const WHITE: Self = Self::new(255, 255, 255);
+    pub const GRAY: Self = Self::new(128, 128, 128);
+    pub const RED: Self = Self::new(255, 0, 0);
+    /// full green. CSS green is darker, at 128.
+    pub const LIME: Self = Self::new(0, 255, 0);
+    pub const BLUE: Self = Self::new(0, 0, 255);
+    pub const YELLOW: Self = Self::new(255, 255, 0);
+    pub const CYAN: Self = Self::new(0, 255, 255);
+    pub const MAGENTA: Self = Self::new(255, 0, 255);
+
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+
+    /// color from 0xRRGGBB, usable in constants
+    pub const fn from_hex(value: u32) -> Self {
+        Self::new((value >> 16) as u8, (value >> 8) as u8, value as u8)
+    }
+
+    /// sum of the differences of each channel, from 0 to 765. See perceptual_distance for how
+    /// different colors look.
+    pub fn distance_from(&self, rhs: Self) -> u16 {
+        self.red.abs_diff(rhs.red) as u16
+            + self.green.abs_diff(rhs.green) as u16
+            + self.blue.abs_diff(rhs.blue) as u16
     }
 }
 
@@ -39,4 +56,155 @@ impl From<Color> for u32 {
     }
 }
 
+/// Formats as #rrggbb, which parses back into the same color.
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:06x}", u32::from(*self))
+    }
+}
+
+/// Why a string couldn't be parsed into a Color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// starts with # but isn't #RGB or #RRGGBB
+    HEX(String),
+    /// starts with rgb( but isn't rgb(r, g, b) with each channel from 0 to 255
+    RGB(String),
+    /// isn't a CSS name, pico8:name, pico8:index or db32:index
+    NAME(String),
+}
+
+impl Display for ParseColorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HEX(s) => write!(f, "invalid hex color {s:?}, expected #RGB or #RRGGBB"),
+            Self::RGB(s) => write!(
+                f,
+                "invalid rgb color {s:?}, expected rgb(r, g, b) with channels from 0 to 255"
+            ),
+            Self::NAME(s) => write!(
+                f,
+                "unknown color {s:?}, expected a CSS name, pico8:name, pico8:index or db32:index"
+            ),
+        }
+    }
+}
+
+impl Error for ParseColorError {}
+
+/// Parses #RGB, #RRGGBB, rgb(r, g, b), CSS names such as rebeccapurple, pico8:name or
+/// pico8:index, and db32:index. Case and surrounding whitespace are ignored.
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let lower = trimmed.to_ascii_lowercase();
+        if let Some(hex) = trimmed.strip_prefix('#') {
+            let error = || ParseColorError::HEX(trimmed.to_string());
+            let digits = hex
+                .chars()
+                .map(|c| c.to_digit(16).map(|d| d as u8))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(error)?;
+            match digits[..] {
+                [r, g, b] => Ok(Self::new(r * 17, g * 17, b * 17)),
+                [r1, r2, g1, g2, b1, b2] => Ok(Self::new(r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2)),
+                _ => Err(error()),
+            }
+        } else if let Some(channels) = lower.strip_prefix("rgb(") {
+            let error = || ParseColorError::RGB(trimmed.to_string());
+            let channels = channels
+                .strip_suffix(')')
+                .ok_or_else(error)?
+                .split(',')
+                .map(|c| c.trim().parse::<u8>().ok())
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(error)?;
+            match channels[..] {
+                [red, green, blue] => Ok(Self::new(red, green, blue)),
+                _ => Err(error()),
+            }
+        } else {
+            match lower.split_once(':') {
+                Some(("pico8", name)) => named_colors::pico8(name.trim()),
+                Some(("db32", index)) => named_colors::db32(index.trim()),
+                Some(_) => None,
+                None => named_colors::css(&lower),
+            }
+            .ok_or_else(|| ParseColorError::NAME(trimmed.to_string()))
+        }
+    }
+}
+
 pub type Pixel = Option<Color>;
+
+/// Color with alpha. An alpha of 0 is fully transparent, 255 fully opaque.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rgba {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    pub alpha: u8,
+}
+
+impl Rgba {
+    pub fn new(color: Color, alpha: u8) -> Self {
+        Self {
+            red: color.red,
+            green: color.green,
+            blue: color.blue,
+            alpha,
+        }
+    }
+
+    /// the color without alpha
+    pub fn color(&self) -> Color {
+        Color {
+            red: self.red,
+            green: self.green,
+            blue: self.blue,
+        }
+    }
+
+    /// scales alpha by opacity between 0 and 1
+    pub fn fade(self, opacity: f32) -> Self {
+        Self {
+            alpha: (self.alpha as f32 * opacity.clamp(0., 1.)).round() as u8,
+            ..self
+        }
+    }
+
+    /// composites self over below (source over)
+    pub fn over(self, below: Self) -> Self {
+        let (above_alpha, below_alpha) = (self.alpha as f32 / 255., below.alpha as f32 / 255.);
+        let alpha = above_alpha + below_alpha * (1. - above_alpha);
+        if alpha == 0. {
+            return Self::default();
+        }
+        let channel = |above: u8, below: u8| {
+            ((above as f32 * above_alpha + below as f32 * below_alpha * (1. - above_alpha)) / alpha)
+                .round() as u8
+        };
+        Self {
+            red: channel(self.red, below.red),
+            green: channel(self.green, below.green),
+            blue: channel(self.blue, below.blue),
+            alpha: (alpha * 255.).round() as u8,
+        }
+    }
+}
+
+impl From<Color> for Rgba {
+    fn from(value: Color) -> Self {
+        Self::new(value, u8::MAX)
+    }
+}
+
+/// None becomes fully transparent
+impl From<Pixel> for Rgba {
+    fn from(value: Pixel) -> Self {
+        value.map(Self::from).unwrap_or_default()
+    }
+}
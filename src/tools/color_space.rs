@@ -0,0 +1,299 @@
+use super::color::Color;
+
+/// Hue in degrees from 0 to 360, saturation and value from 0 to 1.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hsv {
+    pub hue: f32,
+    pub saturation: f32,
+    pub value: f32,
+}
+
+/// Hue in degrees from 0 to 360, saturation and lightness from 0 to 1.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hsl {
+    pub hue: f32,
+    pub saturation: f32,
+    pub lightness: f32,
+}
+
+/// Perceptual color space where equal distances look about equally different. lightness is
+/// from 0 to 1, a and b are roughly from -0.4 to 0.4.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Oklab {
+    pub lightness: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+/// Space colors are interpolated in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorSpace {
+    #[default]
+    RGB,
+    HSV,
+    HSL,
+    /// usually the most even looking
+    OKLAB,
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// interpolates hues in degrees the short way around
+fn lerp_hue(a: f32, b: f32, t: f32) -> f32 {
+    let difference = (b - a + 540.).rem_euclid(360.) - 180.;
+    (a + difference * t).rem_euclid(360.)
+}
+
+/// red, green and blue from 0 to 1
+fn channels(color: Color) -> [f32; 3] {
+    [color.red, color.green, color.blue].map(|c| c as f32 / 255.)
+}
+
+fn from_channels([red, green, blue]: [f32; 3]) -> Color {
+    let channel = |c: f32| (c * 255.).round().clamp(0., 255.) as u8;
+    Color {
+        red: channel(red),
+        green: channel(green),
+        blue: channel(blue),
+    }
+}
+
+/// hue in degrees of red, green and blue with given max and range of channels
+fn hue([red, green, blue]: [f32; 3], max: f32, range: f32) -> f32 {
+    if range == 0. {
+        0.
+    } else if max == red {
+        (60. * (green - blue) / range).rem_euclid(360.)
+    } else if max == green {
+        60. * (blue - red) / range + 120.
+    } else {
+        60. * (red - green) / range + 240.
+    }
+}
+
+/// red, green and blue of hue with given chroma and lightest channel offset
+fn from_hue(hue: f32, chroma: f32, offset: f32) -> Color {
+    let hue = hue.rem_euclid(360.) / 60.;
+    let x = chroma * (1. - (hue.rem_euclid(2.) - 1.).abs());
+    let [red, green, blue] = match hue as usize {
+        0 => [chroma, x, 0.],
+        1 => [x, chroma, 0.],
+        2 => [0., chroma, x],
+        3 => [0., x, chroma],
+        4 => [x, 0., chroma],
+        _ => [chroma, 0., x],
+    };
+    from_channels([red + offset, green + offset, blue + offset])
+}
+
+impl From<Color> for Hsv {
+    fn from(value: Color) -> Self {
+        let rgb = channels(value);
+        let max = rgb.iter().copied().fold(0., f32::max);
+        let range = max - rgb.iter().copied().fold(1., f32::min);
+        Self {
+            hue: hue(rgb, max, range),
+            saturation: if max == 0. { 0. } else { range / max },
+            value: max,
+        }
+    }
+}
+
+impl From<Hsv> for Color {
+    fn from(value: Hsv) -> Self {
+        let chroma = value.value * value.saturation;
+        from_hue(value.hue, chroma, value.value - chroma)
+    }
+}
+
+impl From<Color> for Hsl {
+    fn from(value: Color) -> Self {
+        let rgb = channels(value);
+        let max = rgb.iter().copied().fold(0., f32::max);
+        let min = rgb.iter().copied().fold(1., f32::min);
+        let lightness = (max + min) / 2.;
+        let range = max - min;
+        Self {
+            hue: hue(rgb, max, range),
+            saturation: if range == 0. {
+                0.
+            } else {
+                range / (1. - (2. * lightness - 1.).abs())
+            },
+            lightness,
+        }
+    }
+}
+
+impl From<Hsl> for Color {
+    fn from(value: Hsl) -> Self {
+        let chroma = (1. - (2. * value.lightness - 1.).abs()) * value.saturation;
+        from_hue(value.hue, chroma, value.lightness - chroma / 2.)
+    }
+}
+
+/// sRGB channel to linear light
+fn linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// linear light to sRGB channel
+fn gamma(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1. / 2.4) - 0.055
+    }
+}
+
+impl From<Color> for Oklab {
+    fn from(value: Color) -> Self {
+        let [red, green, blue] = channels(value).map(linear);
+        let l = (0.41222147 * red + 0.53633254 * green + 0.05144599 * blue).cbrt();
+        let m = (0.2119035 * red + 0.6806995 * green + 0.10739696 * blue).cbrt();
+        let s = (0.08830246 * red + 0.28171884 * green + 0.6299787 * blue).cbrt();
+        Self {
+            lightness: 0.21045426 * l + 0.7936178 * m - 0.00407205 * s,
+            a: 1.9779985 * l - 2.4285922 * m + 0.4505937 * s,
+            b: 0.02590404 * l + 0.78277177 * m - 0.80867577 * s,
+        }
+    }
+}
+
+impl From<Oklab> for Color {
+    fn from(value: Oklab) -> Self {
+        let Oklab { lightness, a, b } = value;
+        let l = (lightness + 0.39633778 * a + 0.21580376 * b).powi(3);
+        let m = (lightness - 0.10556135 * a - 0.06385417 * b).powi(3);
+        let s = (lightness - 0.08948418 * a - 1.2914856 * b).powi(3);
+        from_channels(
+            [
+                4.0767417 * l - 3.3077116 * m + 0.23096994 * s,
+                -1.268438 * l + 2.6097574 * m - 0.34131938 * s,
+                -0.0041960864 * l - 0.7034186 * m + 1.7076147 * s,
+            ]
+            .map(|c| gamma(c.clamp(0., 1.))),
+        )
+    }
+}
+
+impl Hsv {
+    /// interpolates towards other by t from 0 to 1, hue the short way around
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        Self {
+            hue: lerp_hue(self.hue, other.hue, t),
+            saturation: lerp(self.saturation, other.saturation, t),
+            value: lerp(self.value, other.value, t),
+        }
+    }
+}
+
+impl Hsl {
+    /// interpolates towards other by t from 0 to 1, hue the short way around
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        Self {
+            hue: lerp_hue(self.hue, other.hue, t),
+            saturation: lerp(self.saturation, other.saturation, t),
+            lightness: lerp(self.lightness, other.lightness, t),
+        }
+    }
+}
+
+impl Oklab {
+    /// interpolates towards other by t from 0 to 1
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        Self {
+            lightness: lerp(self.lightness, other.lightness, t),
+            a: lerp(self.a, other.a, t),
+            b: lerp(self.b, other.b, t),
+        }
+    }
+
+    /// euclidean distance, about 0.02 for barely noticeable differences
+    pub fn distance_from(&self, rhs: Self) -> f32 {
+        ((self.lightness - rhs.lightness).powi(2)
+            + (self.a - rhs.a).powi(2)
+            + (self.b - rhs.b).powi(2))
+        .sqrt()
+    }
+}
+
+impl Color {
+    /// interpolates towards other by t from 0 to 1 in red, green and blue
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        let (a, b) = (channels(self), channels(other));
+        from_channels([0, 1, 2].map(|i| lerp(a[i], b[i], t)))
+    }
+
+    /// interpolates towards other by t from 0 to 1 in given space
+    pub fn lerp_in(self, other: Self, t: f32, space: ColorSpace) -> Self {
+        match space {
+            ColorSpace::RGB => self.lerp(other, t),
+            ColorSpace::HSV => Hsv::from(self).lerp(other.into(), t).into(),
+            ColorSpace::HSL => Hsl::from(self).lerp(other.into(), t).into(),
+            ColorSpace::OKLAB => Oklab::from(self).lerp(other.into(), t).into(),
+        }
+    }
+
+    /// how different the colors look, measured in Oklab. 0 for the same color, about 1 between
+    /// black and white.
+    pub fn perceptual_distance(&self, rhs: Self) -> f32 {
+        Oklab::from(*self).distance_from(rhs.into())
+    }
+}
+
+/// Colors at positions from 0 to 1, interpolated between.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gradient {
+    /// positions and colors, sorted by position
+    stops: Vec<(f32, Color)>,
+    pub space: ColorSpace,
+}
+
+impl Gradient {
+    /// gradient through stops of positions and colors, in any order
+    pub fn new(mut stops: Vec<(f32, Color)>, space: ColorSpace) -> Self {
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { stops, space }
+    }
+
+    /// gradient through colors spaced evenly from 0 to 1
+    pub fn even(colors: &[Color], space: ColorSpace) -> Self {
+        let last = colors.len().saturating_sub(1).max(1) as f32;
+        Self::new(
+            colors
+                .iter()
+                .enumerate()
+                .map(|(i, color)| (i as f32 / last, *color))
+                .collect::<Vec<_>>(),
+            space,
+        )
+    }
+
+    pub fn stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+
+    /// color at position. Positions before the first stop or after the last take its color.
+    pub fn sample(&self, position: f32) -> Color {
+        let next = self.stops.partition_point(|(stop, _)| *stop <= position);
+        match (self.stops.get(next.wrapping_sub(1)), self.stops.get(next)) {
+            (Some((start, a)), Some((end, b))) => {
+                a.lerp_in(*b, (position - start) / (end - start), self.space)
+            }
+            (Some((_, color)), None) | (None, Some((_, color))) => *color,
+            (None, None) => Color::default(),
+        }
+    }
+}
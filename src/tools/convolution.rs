@@ -0,0 +1,209 @@
+use super::{
+    color::Color,
+    dual_trait::Algebra,
+    matrix::Matrix,
+    transform::{Dimensions, Position},
+};
+
+/// How values past the edges of a matrix are read during convolution.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum EdgeMode {
+    /// repeats the nearest edge value
+    #[default]
+    CLAMP,
+    /// continues on the opposite edge
+    WRAP,
+    /// counts as black, so blurs fade out towards the edges
+    TRANSPARENT,
+}
+
+impl EdgeMode {
+    /// WRAP for wrapping matrices, CLAMP otherwise
+    pub fn of<T: Clone>(matrix: &Matrix<T>) -> Self {
+        if matrix.wrapping {
+            Self::WRAP
+        } else {
+            Self::CLAMP
+        }
+    }
+}
+
+/// Weights applied to the neighbourhood of each color. The center of weights lines up with the
+/// color being computed.
+#[derive(Clone, Debug)]
+pub struct Kernel {
+    pub weights: Matrix<f32>,
+    /// added to each channel after weighting
+    pub bias: f32,
+}
+
+impl Kernel {
+    /// kernel with weights given row by row. Panics if there isn't a weight for each position of
+    /// dimensions.
+    pub fn new(weights: Vec<f32>, dimensions: Dimensions, bias: f32) -> Self {
+        assert_eq!(
+            weights.len(),
+            dimensions.area(),
+            "kernel needs a weight for each position"
+        );
+        Self {
+            weights: Matrix {
+                values: weights,
+                dimensions,
+                wrapping: false,
+            },
+            bias,
+        }
+    }
+
+    /// averages a line of 2 * radius + 1 values. Horizontal if horizontal, vertical otherwise
+    pub fn box_line(radius: usize, horizontal: bool) -> Self {
+        let length = radius * 2 + 1;
+        Self::line(vec![1. / length as f32; length], horizontal)
+    }
+
+    /// weighs a line of values by a normal distribution with standard deviation sigma.
+    /// Horizontal if horizontal, vertical otherwise. A sigma of 0 or less leaves values as they
+    /// are.
+    pub fn gaussian_line(sigma: f32, horizontal: bool) -> Self {
+        if sigma.is_nan() || sigma <= 0. {
+            return Self::line(vec![1.], horizontal);
+        }
+        let radius = (sigma * 3.).ceil().max(0.) as isize;
+        let weights = (-radius..=radius)
+            .map(|i| (-((i * i) as f32) / (2. * sigma * sigma)).exp())
+            .collect::<Vec<_>>();
+        let total = weights.iter().sum::<f32>();
+        Self::line(weights.iter().map(|w| w / total).collect(), horizontal)
+    }
+
+    fn line(weights: Vec<f32>, horizontal: bool) -> Self {
+        let dimensions = if horizontal {
+            Dimensions::new(weights.len(), 1)
+        } else {
+            Dimensions::new(1, weights.len())
+        };
+        Self::new(weights, dimensions, 0.)
+    }
+
+    pub fn sharpen() -> Self {
+        Self::new(
+            vec![0., -1., 0., -1., 5., -1., 0., -1., 0.],
+            Dimensions::splat(3),
+            0.,
+        )
+    }
+
+    pub fn emboss() -> Self {
+        Self::new(
+            vec![-2., -1., 0., -1., 1., 1., 0., 1., 2.],
+            Dimensions::splat(3),
+            0.,
+        )
+    }
+
+    /// horizontal gradient of the Sobel operator
+    pub fn sobel_x() -> Self {
+        Self::new(
+            vec![-1., 0., 1., -2., 0., 2., -1., 0., 1.],
+            Dimensions::splat(3),
+            0.,
+        )
+    }
+
+    /// vertical gradient of the Sobel operator
+    pub fn sobel_y() -> Self {
+        Self::new(
+            vec![-1., -2., -1., 0., 0., 0., 1., 2., 1.],
+            Dimensions::splat(3),
+            0.,
+        )
+    }
+}
+
+impl Matrix<Color> {
+    /// returns the matrix with each color replaced by the weighted sum of its neighbourhood
+    pub fn convolve(&self, kernel: &Kernel, edge: EdgeMode) -> Self {
+        let mut matrix = Matrix::new(self.dimensions, self.wrapping);
+        matrix.fill_with(|position| {
+            let [red, green, blue] = self.weigh(position, kernel, edge);
+            let channel = |value: f32| (value + kernel.bias).round().clamp(0., 255.) as u8;
+            Color {
+                red: channel(red),
+                green: channel(green),
+                blue: channel(blue),
+            }
+        });
+        matrix
+    }
+
+    /// weighted sum of each channel around position, without bias
+    fn weigh(&self, position: Position, kernel: &Kernel, edge: EdgeMode) -> [f32; 3] {
+        let center = Position::from(kernel.weights.dimensions.div(Dimensions::splat(2)));
+        kernel
+            .weights
+            .enumerate()
+            .fold([0.; 3], |sum, (offset, weight)| {
+                let position = position.add(offset).sub(center);
+                let color = match edge {
+                    EdgeMode::CLAMP => self.get_wrap(Position::new(
+                        position.x.clamp(0, self.dimensions.width as isize - 1),
+                        position.y.clamp(0, self.dimensions.height as isize - 1),
+                    )),
+                    EdgeMode::WRAP => self.get_wrap(position),
+                    EdgeMode::TRANSPARENT
+                        if position.x < 0
+                            || position.y < 0
+                            || position.x as usize >= self.dimensions.width
+                            || position.y as usize >= self.dimensions.height =>
+                    {
+                        return sum
+                    }
+                    EdgeMode::TRANSPARENT => self.get_wrap(position),
+                };
+                [
+                    sum[0] + color.red as f32 * weight,
+                    sum[1] + color.green as f32 * weight,
+                    sum[2] + color.blue as f32 * weight,
+                ]
+            })
+    }
+
+    /// averages each color with its neighbours up to radius away
+    pub fn box_blur(&self, radius: usize, edge: EdgeMode) -> Self {
+        self.convolve(&Kernel::box_line(radius, true), edge)
+            .convolve(&Kernel::box_line(radius, false), edge)
+    }
+
+    /// blurs by a normal distribution with standard deviation sigma
+    pub fn gaussian_blur(&self, sigma: f32, edge: EdgeMode) -> Self {
+        self.convolve(&Kernel::gaussian_line(sigma, true), edge)
+            .convolve(&Kernel::gaussian_line(sigma, false), edge)
+    }
+
+    pub fn sharpen(&self, edge: EdgeMode) -> Self {
+        self.convolve(&Kernel::sharpen(), edge)
+    }
+
+    pub fn emboss(&self, edge: EdgeMode) -> Self {
+        self.convolve(&Kernel::emboss(), edge)
+    }
+
+    /// returns a grayscale matrix that is bright where brightness changes sharply (Sobel)
+    pub fn sobel(&self, edge: EdgeMode) -> Self {
+        let (horizontal, vertical) = (Kernel::sobel_x(), Kernel::sobel_y());
+        let luma = |[red, green, blue]: [f32; 3]| 0.299 * red + 0.587 * green + 0.114 * blue;
+        let mut matrix = Matrix::new(self.dimensions, self.wrapping);
+        matrix.fill_with(|position| {
+            let x = luma(self.weigh(position, &horizontal, edge));
+            let y = luma(self.weigh(position, &vertical, edge));
+            let value = (x * x + y * y).sqrt().round().clamp(0., 255.) as u8;
+            Color {
+                red: value,
+                green: value,
+                blue: value,
+            }
+        });
+        matrix
+    }
+}
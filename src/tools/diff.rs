@@ -0,0 +1,51 @@
+use super::{
+    dual_trait::Algebra,
+    matrix::Matrix,
+    transform::{Dimensions, Position, Rect},
+};
+
+impl<T: Default + Clone + Sync + Send + PartialEq> Matrix<T> {
+    /// Returns the areas that differ from previous. Consecutive changed rows are merged into one
+    /// area spanning their changes. If the dimensions differ, the whole matrix is one area.
+    pub fn diff(&self, previous: &Self) -> Vec<Rect> {
+        if !self.dimensions.eq(previous.dimensions) {
+            return vec![Rect::new(Position::splat(0), self.dimensions)];
+        }
+        let width = self.dimensions.width.max(1);
+        let spans = self
+            .values
+            .chunks(width)
+            .zip(previous.values.chunks(width))
+            .map(|(row, previous)| {
+                let mut changed = row
+                    .iter()
+                    .zip(previous)
+                    .enumerate()
+                    .filter(|(_, (t, u))| t != u)
+                    .map(|(x, _)| x);
+                changed
+                    .next()
+                    .map(|first| (first, changed.last().unwrap_or(first)))
+            });
+
+        let mut rects = Vec::new();
+        let mut current: Option<(usize, usize, usize)> = None;
+        spans.chain([None]).enumerate().for_each(|(y, span)| {
+            current = match (current, span) {
+                (Some((top, left, right)), Some((first, last))) => {
+                    Some((top, left.min(first), right.max(last)))
+                }
+                (None, Some((first, last))) => Some((y, first, last)),
+                (Some((top, left, right)), None) => {
+                    rects.push(Rect::new(
+                        Position::new(left as isize, top as isize),
+                        Dimensions::new(right + 1 - left, y - top),
+                    ));
+                    None
+                }
+                (None, None) => None,
+            }
+        });
+        rects
+    }
+}
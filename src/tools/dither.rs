@@ -0,0 +1,180 @@
+use crate::graphics::palette::Palette;
+
+use super::{
+    color::{Color, Pixel, Rgba},
+    matrix::Matrix,
+    transform::Dimensions,
+};
+
+/// How the error of reducing colors is hidden by patterns.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Dither {
+    /// nearest color only, which bands on gradients
+    #[default]
+    NONE,
+    /// ordered dithering with a 2x2 Bayer matrix
+    BAYER2,
+    /// ordered dithering with a 4x4 Bayer matrix
+    BAYER4,
+    /// ordered dithering with an 8x8 Bayer matrix
+    BAYER8,
+    /// spreads all of the error to the 4 following neighbours
+    FLOYDSTEINBERG,
+    /// spreads 3/4 of the error to 6 following neighbours, keeping more contrast
+    ATKINSON,
+}
+
+impl Dither {
+    /// width of the Bayer matrix, 0 if not ordered
+    fn order(&self) -> usize {
+        match self {
+            Self::BAYER2 => 2,
+            Self::BAYER4 => 4,
+            Self::BAYER8 => 8,
+            _ => 0,
+        }
+    }
+
+    /// ordered threshold at (x, y) between -0.5 and 0.5, 0 if not ordered
+    fn threshold(&self, x: usize, y: usize) -> f32 {
+        let order = self.order();
+        if order == 0 {
+            return 0.;
+        }
+        // sums the 2x2 pattern at each scale, the finest scale weighing the most
+        let (mut value, mut size, mut weight) = (0, 1, order * order / 4);
+        while size < order {
+            let (bx, by) = ((x / size) % 2, (y / size) % 2);
+            value += [[0, 2], [3, 1]][by][bx] * weight;
+            size *= 2;
+            weight /= 4;
+        }
+        (value as f32 + 0.5) / (order * order) as f32 - 0.5
+    }
+
+    /// offsets of following neighbours and the share of error they receive
+    fn diffusion(&self) -> &'static [(isize, usize, f32)] {
+        match self {
+            Self::FLOYDSTEINBERG => &[
+                (1, 0, 7. / 16.),
+                (-1, 1, 3. / 16.),
+                (0, 1, 5. / 16.),
+                (1, 1, 1. / 16.),
+            ],
+            Self::ATKINSON => &[
+                (1, 0, 1. / 8.),
+                (2, 0, 1. / 8.),
+                (-1, 1, 1. / 8.),
+                (0, 1, 1. / 8.),
+                (1, 1, 1. / 8.),
+                (0, 2, 1. / 8.),
+            ],
+            _ => &[],
+        }
+    }
+
+    /// Chooses a result for each of values row by row with pick, which returns the result and
+    /// the value it stands for. Ordered thresholds are scaled by spread, and error diffusion
+    /// spreads the difference between a value and what pick chose.
+    fn apply<T, const N: usize>(
+        &self,
+        dimensions: Dimensions,
+        mut values: Vec<[f32; N]>,
+        spread: f32,
+        mut pick: impl FnMut([f32; N]) -> (T, [f32; N]),
+    ) -> Vec<T> {
+        let width = dimensions.width;
+        (0..values.len())
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                let threshold = self.threshold(x, y) * spread;
+                let (result, chosen) = pick(values[i].map(|v| v + threshold));
+                let (value, mut error) = (values[i], chosen);
+                error.iter_mut().zip(value).for_each(|(e, v)| *e = v - *e);
+                self.diffusion().iter().for_each(|(dx, dy, share)| {
+                    let nx = x as isize + dx;
+                    if nx >= 0 && (nx as usize) < width {
+                        if let Some(value) = values.get_mut(nx as usize + (y + dy) * width) {
+                            value
+                                .iter_mut()
+                                .zip(&error)
+                                .for_each(|(v, e)| *v += e * share)
+                        }
+                    }
+                });
+                result
+            })
+            .collect::<Vec<_>>()
+    }
+}
+
+impl Matrix<Color> {
+    /// Reduces the matrix to the colors of palette, returning the palette index of each color.
+    /// dither hides the banding between colors. Thresholds, error and the nearest color are
+    /// all worked out in red, green and blue, so a flat color dithers to the mix that averages
+    /// back to it.
+    pub fn dither(&self, palette: &Palette, dither: Dither) -> Matrix<u8> {
+        let channels = |color: Color| [color.red, color.green, color.blue].map(|c| c as f32);
+        let entries = palette
+            .colors()
+            .map(|(index, color)| (index, channels(color)))
+            .collect::<Vec<_>>();
+        // ordered patterns are scaled to roughly the distance between palette colors
+        let spread = 255. / ((entries.len() as f32).cbrt() - 1.).max(1.);
+        let values = self
+            .values
+            .iter()
+            .copied()
+            .map(channels)
+            .collect::<Vec<_>>();
+        let indices = dither.apply(self.dimensions, values, spread, |value| {
+            let distance = |entry: &[f32; 3]| {
+                entry
+                    .iter()
+                    .zip(value)
+                    .map(|(e, v)| (e - v) * (e - v))
+                    .sum::<f32>()
+            };
+            entries
+                .iter()
+                .min_by(|a, b| distance(&a.1).total_cmp(&distance(&b.1)))
+                .copied()
+                .unwrap_or((0, value))
+        });
+        Matrix {
+            values: indices,
+            dimensions: self.dimensions,
+            wrapping: self.wrapping,
+        }
+    }
+}
+
+impl Matrix<Rgba> {
+    /// Turns partial transparency into a pattern of opaque and transparent pixels, such as for
+    /// fading sprites in and out.
+    pub fn dither_alpha(&self, dither: Dither) -> Matrix<Pixel> {
+        let values = self
+            .values
+            .iter()
+            .map(|rgba| [rgba.alpha as f32 / 255.])
+            .collect::<Vec<_>>();
+        let opaque = dither.apply(self.dimensions, values, 1., |[alpha]| {
+            if alpha >= 0.5 {
+                (true, [1.])
+            } else {
+                (false, [0.])
+            }
+        });
+        Matrix {
+            values: self
+                .values
+                .iter()
+                .zip(opaque)
+                .map(|(rgba, opaque)| opaque.then(|| rgba.color()))
+                .collect::<Vec<_>>(),
+            dimensions: self.dimensions,
+            wrapping: self.wrapping,
+        }
+    }
+}
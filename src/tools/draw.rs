@@ -0,0 +1,167 @@
+use super::{
+    dual_trait::Algebra,
+    matrix::Matrix,
+    transform::{Dimensions, Position},
+};
+
+/// Drawing works through Matrix::set, so shapes are clipped at the edges of the matrix or wrap
+/// around if the matrix is wrapping.
+impl<T: Default + Clone + Sync + Send> Matrix<T> {
+    /// draws a line from start to end, both included (Bresenham)
+    pub fn draw_line(&mut self, start: Position, end: Position, value: T) {
+        let (dx, dy) = ((end.x - start.x).abs(), -(end.y - start.y).abs());
+        let step = Position::new((end.x - start.x).signum(), (end.y - start.y).signum());
+        let mut error = dx + dy;
+        let mut position = start;
+        loop {
+            self.set(position, value.clone());
+            if position.eq(end) {
+                break;
+            }
+            let doubled = error * 2;
+            if doubled >= dy {
+                error += dy;
+                position.x += step.x;
+            }
+            if doubled <= dx {
+                error += dx;
+                position.y += step.y;
+            }
+        }
+    }
+
+    /// draws lines between each point and the next, closing back to the first
+    pub fn draw_polygon(&mut self, points: &[Position], value: T) {
+        points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .for_each(|(start, end)| self.draw_line(*start, *end, value.clone()))
+    }
+
+    /// draws the outline of a rectangle with width and height starting at (x, y)
+    pub fn draw_rect(&mut self, position: Position, dimensions: Dimensions, value: T) {
+        if dimensions.area() == 0 {
+            return;
+        }
+        let end = position.add(Position::from(dimensions).sub(Position::splat(1)));
+        self.draw_polygon(
+            &[
+                position,
+                Position::new(end.x, position.y),
+                end,
+                Position::new(position.x, end.y),
+            ],
+            value,
+        )
+    }
+
+    /// fills a rectangle with width and height starting at (x, y)
+    pub fn fill_rect(&mut self, position: Position, dimensions: Dimensions, value: T) {
+        (0..dimensions.height as isize).for_each(|y| {
+            (0..dimensions.width as isize)
+                .for_each(|x| self.set(position.add(Position::new(x, y)), value.clone()))
+        })
+    }
+
+    /// draws the outline of a circle around center
+    pub fn draw_circle(&mut self, center: Position, radius: usize, value: T) {
+        self.draw_ellipse(center, Dimensions::splat(radius), value)
+    }
+
+    /// fills a circle around center
+    pub fn fill_circle(&mut self, center: Position, radius: usize, value: T) {
+        self.fill_ellipse(center, Dimensions::splat(radius), value)
+    }
+
+    /// draws the outline of an ellipse around center, with radii as its horizontal and vertical
+    /// radius
+    pub fn draw_ellipse(&mut self, center: Position, radii: Dimensions, value: T) {
+        let (rx, ry) = (radii.width as isize, radii.height as isize);
+        // scanning by both rows and columns leaves no gaps in the steep or flat parts
+        (-ry..=ry).for_each(|y| {
+            let x = extent(rx, ry, y);
+            self.set(center.add(Position::new(x, y)), value.clone());
+            self.set(center.add(Position::new(-x, y)), value.clone());
+        });
+        (-rx..=rx).for_each(|x| {
+            let y = extent(ry, rx, x);
+            self.set(center.add(Position::new(x, y)), value.clone());
+            self.set(center.add(Position::new(x, -y)), value.clone());
+        });
+    }
+
+    /// fills an ellipse around center, with radii as its horizontal and vertical radius
+    pub fn fill_ellipse(&mut self, center: Position, radii: Dimensions, value: T) {
+        let (rx, ry) = (radii.width as isize, radii.height as isize);
+        (-ry..=ry).for_each(|y| {
+            let x = extent(rx, ry, y);
+            (-x..=x).for_each(|x| self.set(center.add(Position::new(x, y)), value.clone()))
+        })
+    }
+
+    /// fills the inside of a polygon by scanlines, using the even-odd rule. A value is filled if
+    /// its position lies inside the polygon, so the right and bottom edges are left out.
+    pub fn fill_polygon(&mut self, points: &[Position], value: T) {
+        let (Some(top), Some(bottom)) = (
+            points.iter().map(|p| p.y).min(),
+            points.iter().map(|p| p.y).max(),
+        ) else {
+            return;
+        };
+        (top..bottom).for_each(|y| {
+            let mut crossings = points
+                .iter()
+                .zip(points.iter().cycle().skip(1))
+                .filter(|(a, b)| (a.y <= y) != (b.y <= y))
+                .map(|(a, b)| {
+                    a.x as f32 + (y - a.y) as f32 * (b.x - a.x) as f32 / (b.y - a.y) as f32
+                })
+                .collect::<Vec<_>>();
+            crossings.sort_by(|a, b| a.total_cmp(b));
+            crossings.chunks_exact(2).for_each(|span| {
+                (span[0].ceil() as isize..span[1].ceil() as isize)
+                    .for_each(|x| self.set(Position::new(x, y), value.clone()))
+            })
+        })
+    }
+
+    /// replaces the area connected to position that holds the same value as position
+    pub fn flood_fill(&mut self, position: Position, value: T)
+    where
+        T: PartialEq,
+    {
+        self.flood_fill_by(position, value, |a, b| a == b)
+    }
+
+    /// replaces the area connected to position where same(value at position, neighbour) is true.
+    /// Useful for types without PartialEq, such as tiles in a TileMap.
+    pub fn flood_fill_by(&mut self, position: Position, value: T, same: impl Fn(&T, &T) -> bool) {
+        let Some(target) = self.get(position).cloned() else {
+            return;
+        };
+        let mut visited = vec![false; self.dimensions.area()];
+        let mut stack = vec![position];
+        while let Some(position) = stack.pop() {
+            let Some(index) = self.some_bound(position) else {
+                continue;
+            };
+            if visited[index] || !same(&target, &self.values[index]) {
+                continue;
+            }
+            visited[index] = true;
+            self.values[index] = value.clone();
+            [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                .iter()
+                .for_each(|(x, y)| stack.push(position.add(Position::new(*x, *y))))
+        }
+    }
+}
+
+/// half the length of the ellipse chord at offset along the other radius
+fn extent(radius: isize, other_radius: isize, offset: isize) -> isize {
+    if other_radius == 0 {
+        return radius;
+    }
+    let ratio = offset as f32 / other_radius as f32;
+    (radius as f32 * (1. - ratio * ratio).max(0.).sqrt()).round() as isize
+}
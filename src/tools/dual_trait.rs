@@ -77,6 +77,7 @@ pub trait Algebra: Sized + Debug {
         (self.first(), self.last())
     }
 
+    #[allow(clippy::wrong_self_convention)]
     fn into_dual<T: Algebra<Item = Self::Item>>(&self) -> T {
         T::new(self.first(), self.last())
     }
@@ -0,0 +1,18 @@
+use super::{matrix::Matrix, transform::Position};
+
+/// Grid is anything values can be read from and written to by position, such as a Matrix or a
+/// ChunkedMatrix.
+pub trait Grid<T>: Sync + Send {
+    fn get(&self, position: Position) -> Option<&T>;
+    fn set(&mut self, position: Position, value: T);
+}
+
+impl<T: Default + Clone + Sync + Send> Grid<T> for Matrix<T> {
+    fn get(&self, position: Position) -> Option<&T> {
+        Matrix::get(self, position)
+    }
+
+    fn set(&mut self, position: Position, value: T) {
+        Matrix::set(self, position, value)
+    }
+}
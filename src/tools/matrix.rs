@@ -1,36 +1,77 @@
-use std::fmt::Debug;
+use std::{
+    fmt::{self, Debug, Display, Formatter},
+    ops::{Index, IndexMut},
+};
 
 use super::{
     dual_trait::Algebra,
-    transform::{Dimensions, Position, Rotation},
+    parallel,
+    transform::{Dimensions, Orientation, Position, Rect, Rotation},
+    view::MatrixView,
 };
 
-/// Matrix is a 2D representation of a vector.
-
+/// Matrix is a 2D representation of a vector. With the serde feature, loading fails if the number
+/// of values doesn't match the dimensions.
 #[derive(Default, Clone, Debug)]
-pub struct Matrix<T: Default + Clone> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Matrix<T: Clone> {
     pub values: Vec<T>,
     pub dimensions: Dimensions,
     /// if true, values outside bounds will wrap
     pub wrapping: bool,
 }
 
-impl<T: Default + Clone + Sync + Send> Matrix<T> {
-    pub fn new(dimensions: Dimensions, wrapping: bool) -> Self {
+impl<T: Clone> Matrix<T> {
+    /// matrix with each value given by func of its position, filled row by row
+    pub fn from_fn(
+        dimensions: Dimensions,
+        wrapping: bool,
+        mut func: impl FnMut(Position) -> T,
+    ) -> Self {
         Self {
-            values: vec![T::default(); dimensions.area()],
+            values: (0..dimensions.area())
+                .map(|i| {
+                    func(Position::new(
+                        (i % dimensions.width) as isize,
+                        (i / dimensions.width) as isize,
+                    ))
+                })
+                .collect::<Vec<_>>(),
             dimensions,
             wrapping,
         }
     }
 
-    fn some_bound(&self, position: Position) -> Option<usize> {
-        match (position, self.dimensions, self.wrapping) {
-            (Position { x, y }, Dimensions { width, height }, true) => {
-                Some((x % width) + ((y % height) * width))
+    /// matrix with given rows. Returns None if the rows are not all the same length.
+    pub fn from_rows<R: IntoIterator<Item = T>>(
+        rows: impl IntoIterator<Item = R>,
+        wrapping: bool,
+    ) -> Option<Self> {
+        let mut values = Vec::new();
+        let (mut width, mut height) = (None, 0);
+        for row in rows {
+            let length = values.len();
+            values.extend(row);
+            if *width.get_or_insert(values.len() - length) != values.len() - length {
+                return None;
             }
-            (Position { x, y }, Dimensions { width, height }, false) if width > x && height > y => {
-                Some(x + (y * width))
+            height += 1;
+        }
+        Some(Self {
+            values,
+            dimensions: Dimensions::new(width.unwrap_or(0), height),
+            wrapping,
+        })
+    }
+
+    /// returns the index into values for position, wrapping if the matrix is wrapping
+    pub(crate) fn some_bound(&self, position: Position) -> Option<usize> {
+        match (position, self.dimensions, self.wrapping) {
+            (_, _, true) => Some(self.wrap_index(position)),
+            (Position { x, y }, Dimensions { width, height }, false)
+                if x >= 0 && y >= 0 && width > x as usize && height > y as usize =>
+            {
+                Some(x as usize + (y as usize * width))
             }
             _ => None,
         }
@@ -54,22 +95,20 @@ impl<T: Default + Clone + Sync + Send> Matrix<T> {
 
     /// will always return value. If out of bounds, it will wrap until in bounds.
     pub fn get_wrap(&self, position: Position) -> &T {
-        self.values
-            .get(
-                (position.x % self.dimensions.width)
-                    + ((position.y % self.dimensions.height) * self.dimensions.width),
-            )
-            .unwrap()
+        let index = self.wrap_index(position);
+        self.values.get(index).unwrap()
     }
 
     /// will always return value. If out of bounds, it will wrap until in bounds.
     pub fn get_wrap_mut(&mut self, position: Position) -> &mut T {
-        self.values
-            .get_mut(
-                (position.x % self.dimensions.width)
-                    + ((position.y % self.dimensions.height) * self.dimensions.width),
-            )
-            .unwrap()
+        let index = self.wrap_index(position);
+        self.values.get_mut(index).unwrap()
+    }
+
+    fn wrap_index(&self, position: Position) -> usize {
+        position.x.rem_euclid(self.dimensions.width as isize) as usize
+            + position.y.rem_euclid(self.dimensions.height as isize) as usize
+                * self.dimensions.width
     }
 
     pub fn set(&mut self, position: Position, value: T) {
@@ -86,28 +125,139 @@ impl<T: Default + Clone + Sync + Send> Matrix<T> {
     }
 
     pub fn enumerate(&self) -> impl Iterator<Item = (Position, &T)> {
-        self.values.iter().enumerate().map(|(i, t)| {
-            (
-                Position::new(i % self.dimensions.width, i / self.dimensions.width),
-                t,
-            )
-        })
+        let width = self.dimensions.width;
+        self.values
+            .iter()
+            .enumerate()
+            .map(move |(i, t)| (Position::new((i % width) as isize, (i / width) as isize), t))
     }
 
     pub fn enumerate_mut(&mut self) -> impl Iterator<Item = (Position, &mut T)> {
-        self.values.iter_mut().enumerate().map(|(i, t)| {
-            (
-                Position::new(i % self.dimensions.width, i / self.dimensions.width),
-                t,
-            )
+        let width = self.dimensions.width;
+        self.values
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, t)| (Position::new((i % width) as isize, (i / width) as isize), t))
+    }
+
+    /// lists each row as a slice
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.values.chunks(self.dimensions.width.max(1))
+    }
+
+    /// lists each row as a mutable slice
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        self.values.chunks_mut(self.dimensions.width.max(1))
+    }
+
+    /// lists each column from top to bottom
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let width = self.dimensions.width;
+        (0..width).map(move |x| self.values.iter().skip(x).step_by(width))
+    }
+
+    /// returns the matrix with rows as columns, which is reflect_diagonal without changing self
+    pub fn transpose(&self) -> Self {
+        Self {
+            values: self.columns().flatten().cloned().collect::<Vec<_>>(),
+            dimensions: Dimensions::new(self.dimensions.height, self.dimensions.width),
+            wrapping: self.wrapping,
+        }
+    }
+
+    /// returns a matrix of the same dimensions with func applied to each value
+    pub fn map<U: Clone>(&self, func: impl FnMut(&T) -> U) -> Matrix<U> {
+        Matrix {
+            values: self.values.iter().map(func).collect::<Vec<_>>(),
+            dimensions: self.dimensions,
+            wrapping: self.wrapping,
+        }
+    }
+
+    /// returns a matrix with func applied to each pair of values at the same position. Only the
+    /// area both matrices cover is kept.
+    pub fn zip_with<U: Clone, V: Clone>(
+        &self,
+        other: &Matrix<U>,
+        mut func: impl FnMut(&T, &U) -> V,
+    ) -> Matrix<V> {
+        let dimensions = Dimensions::new(
+            self.dimensions.width.min(other.dimensions.width),
+            self.dimensions.height.min(other.dimensions.height),
+        );
+        Matrix {
+            values: self
+                .rows()
+                .zip(other.rows())
+                .take(dimensions.height)
+                .flat_map(|(row, other)| row.iter().zip(other).take(dimensions.width))
+                .map(|(t, u)| func(t, u))
+                .collect::<Vec<_>>(),
+            dimensions,
+            wrapping: self.wrapping && other.wrapping,
+        }
+    }
+}
+
+impl<T: Default + Clone + Sync + Send> Matrix<T> {
+    pub fn new(dimensions: Dimensions, wrapping: bool) -> Self {
+        Self {
+            values: vec![T::default(); dimensions.area()],
+            dimensions,
+            wrapping,
+        }
+    }
+
+    /// Intersects the area with width and height starting at (x, y) with the matrix.
+    /// Returns the visible area, which starts in bounds.
+    fn intersect(&self, position: Position, dimensions: Dimensions) -> Rect {
+        intersect(self.dimensions, position, dimensions)
+    }
+
+    /// Keeps only the values of a row-major iterator with width and height starting at (x, y)
+    /// that land inside the matrix.
+    fn clip<I: Iterator>(
+        &self,
+        iter: I,
+        position: Position,
+        dimensions: Dimensions,
+    ) -> impl Iterator<Item = I::Item> {
+        let Rect {
+            position: start,
+            dimensions: visible,
+        } = self.intersect(position, dimensions);
+        let skip = start.sub(position);
+        let skip = Dimensions::new(skip.x as usize, skip.y as usize);
+        iter.take(dimensions.area())
+            .enumerate()
+            .filter_map(move |(i, t)| {
+                let (x, y) = (i % dimensions.width, i / dimensions.width);
+                (x >= skip.width
+                    && x < skip.width + visible.width
+                    && y >= skip.height
+                    && y < skip.height + visible.height)
+                    .then_some(t)
+            })
+    }
+
+    /// Sets each value to func of its position. Rows are filled in parallel with the rayon
+    /// feature.
+    pub(crate) fn fill_with(&mut self, func: impl Fn(Position) -> T + Sync + Send) {
+        parallel::for_each_chunk_mut(&mut self.values, self.dimensions.width, |y, row| {
+            row.iter_mut().enumerate().for_each(|(x, t)| {
+                *t = func(Position::new(x as isize, y as isize));
+            })
         })
     }
 
-    /// Overlays matrix with other given matrix starting at position (x, y).
-    pub fn overlay(&mut self, matrix: &Matrix<T>, position: Position) {
-        self.clamp_mut(position, matrix.dimensions)
-            .zip(matrix.values.iter())
-            .for_each(|(t, u)| *t = u.clone())
+    /// Overlays matrix with other given matrix or view starting at position (x, y).
+    /// Values landing outside the matrix are clipped, or wrap around if the matrix is wrapping.
+    pub fn overlay<'a>(&mut self, matrix: impl Into<MatrixView<'a, T>>, position: Position)
+    where
+        T: 'a,
+    {
+        let view = matrix.into();
+        self.overlay_iter(view.iter(), position, view.dimensions())
     }
 
     /// Overlays iterator onto matrix starting at position (x, y).
@@ -119,20 +269,19 @@ impl<T: Default + Clone + Sync + Send> Matrix<T> {
     ) where
         T: 'a,
     {
-        self.clamp_mut(position, dimensions)
-            .zip(iter)
-            .for_each(|(t, u)| *t = u.clone())
-    }
-
-    /// Overlays matrix only with Some(T) value.
-    pub fn transparent_overlay(&mut self, matrix: &Matrix<Option<T>>, position: Position) {
-        self.clamp_mut(position, matrix.dimensions)
-            .zip(matrix.values.iter())
-            .for_each(|(t, u)| {
-                if let Some(item) = u {
-                    *t = item.clone();
-                }
-            })
+        self.blit(iter, position, dimensions, |t, u| *t = u.clone())
+    }
+
+    /// Overlays matrix or view only with Some(T) value.
+    pub fn transparent_overlay<'a>(
+        &mut self,
+        matrix: impl Into<MatrixView<'a, Option<T>>>,
+        position: Position,
+    ) where
+        T: 'a,
+    {
+        let view = matrix.into();
+        self.transparent_overlay_iter(view.iter().cloned(), position, view.dimensions())
     }
 
     pub fn transparent_overlay_iter<'a>(
@@ -143,23 +292,71 @@ impl<T: Default + Clone + Sync + Send> Matrix<T> {
     ) where
         T: 'a,
     {
-        self.clamp_mut(position, dimensions)
-            .zip(iter)
-            .for_each(|(t, u)| {
-                if let Some(item) = u {
-                    *t = item.clone()
-                }
+        self.blit(iter, position, dimensions, |t, u| {
+            if let Some(item) = u {
+                *t = item
+            }
+        })
+    }
+
+    /// Applies func to each value covered by a row-major iterator with width and height starting
+    /// at (x, y). If the matrix is wrapping, values past an edge continue on the opposite edge,
+    /// otherwise they are clipped.
+    pub(crate) fn blit<U>(
+        &mut self,
+        iter: impl Iterator<Item = U>,
+        position: Position,
+        dimensions: Dimensions,
+        func: impl Fn(&mut T, U),
+    ) {
+        if self.wrapping {
+            let iter = self.clip_wrap(iter, dimensions);
+            self.clamp_wrap_mut(position, dimensions)
+                .zip(iter)
+                .for_each(|(t, u)| func(t, u))
+        } else {
+            let iter = self.clip(iter, position, dimensions);
+            self.clamp_mut(position, dimensions)
+                .zip(iter)
+                .for_each(|(t, u)| func(t, u))
+        }
+    }
+
+    /// Keeps only the values of a row-major iterator with width and height that fit in the
+    /// matrix once. Used alongside clamp_wrap_mut, which visits each value at most once.
+    fn clip_wrap<I: Iterator>(
+        &self,
+        iter: I,
+        dimensions: Dimensions,
+    ) -> impl Iterator<Item = I::Item> {
+        let visible = self.wrap_visible(dimensions);
+        iter.take(dimensions.area())
+            .enumerate()
+            .filter_map(move |(i, t)| {
+                (i % dimensions.width < visible.width && i / dimensions.width < visible.height)
+                    .then_some(t)
             })
     }
 
+    fn wrap_visible(&self, dimensions: Dimensions) -> Dimensions {
+        Dimensions::new(
+            dimensions.width.min(self.dimensions.width),
+            dimensions.height.min(self.dimensions.height),
+        )
+    }
+
     /// Lists values in matrix with width and height starting at (x, y). Has possibility to return
     /// less values because they're out of bounds.
     pub fn clamp(&self, position: Position, dimensions: Dimensions) -> impl Iterator<Item = &T> {
+        let Rect {
+            position: start,
+            dimensions: visible,
+        } = self.intersect(position, dimensions);
         self.values
-            .chunks(self.dimensions.width)
-            .skip(position.y)
-            .take(dimensions.height)
-            .flat_map(move |chunk| chunk.iter().skip(position.x).take(dimensions.width))
+            .chunks(self.dimensions.width.max(1))
+            .skip(start.y as usize)
+            .take(visible.height)
+            .flat_map(move |chunk| chunk.iter().skip(start.x as usize).take(visible.width))
     }
 
     pub fn clamp_mut(
@@ -167,11 +364,15 @@ impl<T: Default + Clone + Sync + Send> Matrix<T> {
         position: Position,
         dimensions: Dimensions,
     ) -> impl Iterator<Item = &mut T> {
+        let Rect {
+            position: start,
+            dimensions: visible,
+        } = self.intersect(position, dimensions);
         self.values
-            .chunks_mut(self.dimensions.width)
-            .skip(position.y)
-            .take(dimensions.height)
-            .flat_map(move |chunk| chunk.iter_mut().skip(position.x).take(dimensions.width))
+            .chunks_mut(self.dimensions.width.max(1))
+            .skip(start.y as usize)
+            .take(visible.height)
+            .flat_map(move |chunk| chunk.iter_mut().skip(start.x as usize).take(visible.width))
     }
 
     pub fn clamp_wrap(
@@ -179,27 +380,42 @@ impl<T: Default + Clone + Sync + Send> Matrix<T> {
         position: Position,
         dimensions: Dimensions,
     ) -> impl Iterator<Item = &T> {
+        let x = position.x.rem_euclid(self.dimensions.width as isize) as usize;
+        let y = position.y.rem_euclid(self.dimensions.height as isize) as usize;
         self.values
             .chunks(self.dimensions.width)
             .cycle()
-            .skip(position.y)
+            .skip(y)
             .take(dimensions.height)
-            .flat_map(move |chunk| chunk.iter().cycle().skip(position.x).take(dimensions.width))
+            .flat_map(move |chunk| chunk.iter().cycle().skip(x).take(dimensions.width))
     }
 
+    /// Lists mutable values in matrix with width and height starting at (x, y), wrapping past the
+    /// edges. Each value is listed at most once, so width and height are limited to those of
+    /// the matrix.
+    pub fn clamp_wrap_mut(
+        &mut self,
+        position: Position,
+        dimensions: Dimensions,
+    ) -> impl Iterator<Item = &mut T> {
+        let width = self.dimensions.width;
+        let x = position.x.rem_euclid(width as isize) as usize;
+        let y = position.y.rem_euclid(self.dimensions.height as isize) as usize;
+        let visible = self.wrap_visible(dimensions);
+        let (head, tail) = self.values.split_at_mut(y * width);
+        tail.chunks_mut(width)
+            .chain(head.chunks_mut(width))
+            .take(visible.height)
+            .flat_map(move |chunk| {
+                let (left, right) = chunk.split_at_mut(x);
+                right.iter_mut().chain(left.iter_mut()).take(visible.width)
+            })
+    }
+
+    /// Copies the values with width and height starting at (x, y) into a new matrix. The new
+    /// matrix only holds the area that is in bounds.
     pub fn clamp_to_matrix(&self, position: Position, dimensions: Dimensions) -> Self {
-        Self {
-            dimensions,
-            values: self
-                .values
-                .chunks(self.dimensions.width)
-                .skip(position.y)
-                .take(dimensions.height)
-                .flat_map(move |chunk| chunk.iter().skip(position.x).take(dimensions.width))
-                .cloned()
-                .collect::<Vec<_>>(),
-            wrapping: false,
-        }
+        self.view(position, dimensions).to_matrix()
     }
 
     /// enumerates then clamps values
@@ -208,13 +424,17 @@ impl<T: Default + Clone + Sync + Send> Matrix<T> {
         position: Position,
         dimensions: Dimensions,
     ) -> impl Iterator<Item = (Position, &T)> {
+        let Rect {
+            position: start,
+            dimensions: visible,
+        } = self.intersect(position, dimensions);
         self.clamp(position, dimensions)
             .enumerate()
             .map(move |(i, t)| {
                 (
-                    position.add(Position::new(
-                        i % self.dimensions.width,
-                        i / self.dimensions.width,
+                    start.add(Position::new(
+                        (i % visible.width) as isize,
+                        (i / visible.width) as isize,
                     )),
                     t,
                 )
@@ -232,8 +452,8 @@ impl<T: Default + Clone + Sync + Send> Matrix<T> {
             .map(move |(i, t)| {
                 (
                     position.add(Position::new(
-                        i % self.dimensions.width,
-                        i / self.dimensions.width,
+                        (i % dimensions.width) as isize,
+                        (i / dimensions.width) as isize,
                     )),
                     t,
                 )
@@ -267,16 +487,14 @@ impl<T: Default + Clone + Sync + Send> Matrix<T> {
     pub fn iter_reflect_horizontal(&self) -> impl Iterator<Item = &T> {
         self.values
             .chunks(self.dimensions.width)
-            .map(|c| c.iter().rev())
-            .flatten()
+            .flat_map(|c| c.iter().rev())
     }
 
     /// returns a mutible iterator of horizontally reflected matrix
     pub fn iter_reflect_horizontal_mut(&mut self) -> impl Iterator<Item = &mut T> {
         self.values
             .chunks_mut(self.dimensions.width)
-            .map(|c| c.iter_mut().rev())
-            .flatten()
+            .flat_map(|c| c.iter_mut().rev())
     }
 
     /// mirrors the matrix on the y = x axis
@@ -305,7 +523,7 @@ impl<T: Default + Clone + Sync + Send> Matrix<T> {
 
     /// returns an iterator of negative diagonally reflected matrix
     pub fn iter_reflect_negative_diagonal(&self) -> impl Iterator<Item = &T> {
-        (self.dimensions.width..0).flat_map(move |i| {
+        (0..self.dimensions.width).rev().flat_map(move |i| {
             self.values
                 .chunks(self.dimensions.width)
                 .rev()
@@ -354,23 +572,29 @@ impl<T: Default + Clone + Sync + Send> Matrix<T> {
         self.values.iter().rev()
     }
 
-    /// rotates the matrix according to rotation direction given
-    pub fn rotate(&mut self, rotation: Rotation) {
-        match rotation {
-            Rotation::DOWN => self.rotate_180(),
-            Rotation::LEFT => self.rotate_left(),
-            Rotation::RIGHT => self.rotate_right(),
-            _ => (),
-        }
+    /// rotates and mirrors the matrix according to orientation given. Accepts a Rotation as well.
+    pub fn rotate(&mut self, orientation: impl Into<Orientation>) {
+        let orientation = orientation.into();
+        self.values = self.iter_rotate(orientation).cloned().collect::<Vec<_>>();
+        self.dimensions = orientation.dimensions(self.dimensions)
     }
 
-    /// returns an iterator of matrix rotated according to given rotation direction
-    pub fn iter_rotate(&self, rotation: Rotation) -> Box<dyn Iterator<Item = &T> + '_> {
-        match rotation {
-            Rotation::DOWN => Box::new(self.iter_rotate_180()),
-            Rotation::LEFT => Box::new(self.iter_rotate_left()),
-            Rotation::RIGHT => Box::new(self.iter_rotate_right()),
-            _ => Box::new(self.values.iter()),
+    /// returns an iterator of matrix rotated and mirrored according to given orientation.
+    /// The dimensions of the result are given by Orientation::dimensions.
+    pub fn iter_rotate(
+        &self,
+        orientation: impl Into<Orientation>,
+    ) -> Box<dyn Iterator<Item = &T> + '_> {
+        let Orientation { rotation, mirrored } = orientation.into();
+        match (rotation, mirrored) {
+            (Rotation::UP, false) => Box::new(self.values.iter()),
+            (Rotation::RIGHT, false) => Box::new(self.iter_rotate_right()),
+            (Rotation::DOWN, false) => Box::new(self.iter_rotate_180()),
+            (Rotation::LEFT, false) => Box::new(self.iter_rotate_left()),
+            (Rotation::UP, true) => Box::new(self.iter_reflect_horizontal()),
+            (Rotation::RIGHT, true) => Box::new(self.iter_reflect_negative_diagonal()),
+            (Rotation::DOWN, true) => Box::new(self.iter_reflect_vertical()),
+            (Rotation::LEFT, true) => Box::new(self.iter_reflect_diagonal()),
         }
     }
 
@@ -386,8 +610,8 @@ impl<T: Default + Clone + Sync + Send> Matrix<T> {
                     (0..subdivision_quantities.width).map(move |x| {
                         self.clamp_to_matrix(
                             Position::new(
-                                x * length_dimensions.width,
-                                y * length_dimensions.height,
+                                (x * length_dimensions.width) as isize,
+                                (y * length_dimensions.height) as isize,
                             ),
                             length_dimensions,
                         )
@@ -399,3 +623,126 @@ impl<T: Default + Clone + Sync + Send> Matrix<T> {
         }
     }
 }
+
+/// Intersects the area with width and height starting at (x, y) with an area of bounds starting
+/// at (0, 0). Returns the visible area, whose position is in bounds and never negative.
+pub(crate) fn intersect(bounds: Dimensions, position: Position, dimensions: Dimensions) -> Rect {
+    let axis = |start: isize, length: usize, bound: usize| {
+        let begin = start.clamp(0, bound as isize);
+        let end = start
+            .saturating_add(length as isize)
+            .clamp(0, bound as isize);
+        (begin, (end - begin) as usize)
+    };
+    let (x, width) = axis(position.x, dimensions.width, bounds.width);
+    let (y, height) = axis(position.y, dimensions.height, bounds.height);
+    Rect::new(Position::new(x, y), Dimensions::new(width, height))
+}
+
+impl<T: Clone> Index<Position> for Matrix<T> {
+    type Output = T;
+
+    /// panics if position is out of bounds and the matrix is not wrapping
+    fn index(&self, position: Position) -> &T {
+        self.get(position).expect("position out of bounds")
+    }
+}
+
+impl<T: Clone> IndexMut<Position> for Matrix<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position).expect("position out of bounds")
+    }
+}
+
+impl<T: Clone> IntoIterator for Matrix<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+impl<'a, T: Clone> IntoIterator for &'a Matrix<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.iter()
+    }
+}
+
+impl<'a, T: Clone> IntoIterator for &'a mut Matrix<T> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.iter_mut()
+    }
+}
+
+/// Implemented by hand since Dimensions uses Algebra::eq rather than PartialEq.
+impl<T: Clone + PartialEq> PartialEq for Matrix<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.dimensions.width == other.dimensions.width
+            && self.dimensions.height == other.dimensions.height
+            && self.wrapping == other.wrapping
+            && self.values == other.values
+    }
+}
+
+impl<T: Clone + Eq> Eq for Matrix<T> {}
+
+/// what a Matrix is loaded as, before its values are checked against its dimensions
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawMatrix<T> {
+    values: Vec<T>,
+    dimensions: Dimensions,
+    wrapping: bool,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Clone + serde::Deserialize<'de>> serde::Deserialize<'de> for Matrix<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let RawMatrix {
+            values,
+            dimensions,
+            wrapping,
+        } = RawMatrix::deserialize(deserializer)?;
+        if values.len() != dimensions.area() {
+            return Err(serde::de::Error::custom(format!(
+                "matrix has {} values, but dimensions {}x{} need {}",
+                values.len(),
+                dimensions.width,
+                dimensions.height,
+                dimensions.area()
+            )));
+        }
+        Ok(Self {
+            values,
+            dimensions,
+            wrapping,
+        })
+    }
+}
+
+/// Writes a row per line, with values right aligned in columns.
+impl<T: Clone + Display> Display for Matrix<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let values = self.values.iter().map(T::to_string).collect::<Vec<_>>();
+        let width = values.iter().map(|v| v.chars().count()).max().unwrap_or(0);
+        for (y, row) in values.chunks(self.dimensions.width.max(1)).enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for (x, value) in row.iter().enumerate() {
+                if x > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{value:>width$}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+//! Splits work by rows. With the rayon feature the rows are processed in parallel, otherwise
+//! one after another. Either way each row gets the same work, so results are identical.
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// calls func with the index and values of each chunk of length values, in parallel with rayon
+pub(crate) fn for_each_chunk_mut<T: Send>(
+    values: &mut [T],
+    length: usize,
+    func: impl Fn(usize, &mut [T]) + Sync + Send,
+) {
+    #[cfg(feature = "rayon")]
+    values
+        .par_chunks_mut(length.max(1))
+        .enumerate()
+        .for_each(|(i, chunk)| func(i, chunk));
+    #[cfg(not(feature = "rayon"))]
+    values
+        .chunks_mut(length.max(1))
+        .enumerate()
+        .for_each(|(i, chunk)| func(i, chunk));
+}
+
+/// maps values into a new vector, in parallel with rayon
+pub(crate) fn map<T: Sync, U: Send>(values: &[T], func: impl Fn(&T) -> U + Sync + Send) -> Vec<U> {
+    #[cfg(feature = "rayon")]
+    return values.par_iter().map(func).collect::<Vec<_>>();
+    #[cfg(not(feature = "rayon"))]
+    return values.iter().map(func).collect::<Vec<_>>();
+}
@@ -0,0 +1,74 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use super::{dual_trait::Algebra, matrix::Matrix, region::Connectivity, transform::Position};
+
+/// distance of values that can't reach any goal
+pub const UNREACHABLE: u32 = u32::MAX;
+
+impl<T: Clone> Matrix<T> {
+    /// Builds a Dijkstra map: the cheapest total cost from each value to the nearest goal.
+    /// cost gives what it takes to enter a value, or None if it can't be entered. Costs below 1
+    /// count as 1, so distances always fall towards a goal and flow_field leads there. Paths wrap
+    /// around the edges of wrapping matrices. Values that can't reach a goal are UNREACHABLE.
+    pub fn dijkstra(
+        &self,
+        goals: impl IntoIterator<Item = Position>,
+        connectivity: Connectivity,
+        cost: impl Fn(Position, &T) -> Option<u32>,
+    ) -> Matrix<u32> {
+        let width = self.dimensions.width;
+        let position = |i: usize| Position::new((i % width) as isize, (i / width) as isize);
+        let mut distances = vec![UNREACHABLE; self.values.len()];
+        let mut heap = BinaryHeap::new();
+        goals
+            .into_iter()
+            .filter_map(|goal| self.some_bound(goal))
+            .for_each(|index| {
+                distances[index] = 0;
+                heap.push(Reverse((0, index)))
+            });
+        while let Some(Reverse((distance, index))) = heap.pop() {
+            if distance > distances[index] {
+                continue;
+            }
+            // cost of stepping from a neighbour into index
+            let Some(step) = cost(position(index), &self.values[index]).map(|c| c.max(1)) else {
+                continue;
+            };
+            let total = distance.saturating_add(step);
+            connectivity.offsets().iter().for_each(|(x, y)| {
+                if let Some(neighbour) = self
+                    .some_bound(position(index).add(Position::new(*x, *y)))
+                    .filter(|n| total < distances[*n])
+                    .filter(|n| cost(position(*n), &self.values[*n]).is_some())
+                {
+                    distances[neighbour] = total;
+                    heap.push(Reverse((total, neighbour)))
+                }
+            })
+        }
+        Matrix {
+            values: distances,
+            dimensions: self.dimensions,
+            wrapping: self.wrapping,
+        }
+    }
+}
+
+impl Matrix<u32> {
+    /// Gives the offset towards the lowest neighbour of each distance in a Dijkstra map, so
+    /// following it leads to the nearest goal. None at goals and values that can't reach one.
+    pub fn flow_field(&self, connectivity: Connectivity) -> Matrix<Option<Position>> {
+        Matrix::from_fn(self.dimensions, self.wrapping, |position| {
+            let distance = *self.get(position).filter(|d| **d != UNREACHABLE)?;
+            connectivity
+                .offsets()
+                .iter()
+                .map(|(x, y)| Position::new(*x, *y))
+                .filter_map(|offset| Some((*self.get(position.add(offset))?, offset)))
+                .filter(|(neighbour, _)| *neighbour < distance)
+                .min_by_key(|(neighbour, _)| *neighbour)
+                .map(|(_, offset)| offset)
+        })
+    }
+}
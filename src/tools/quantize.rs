@@ -0,0 +1,190 @@
+use crate::graphics::palette::Palette;
+
+use super::{
+    color::{Color, Pixel},
+    color_space::Oklab,
+    matrix::Matrix,
+};
+
+/// How the colors of a palette are chosen when reducing a matrix to fewer colors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Quantizer {
+    /// splits the colors in half along their widest channel until there are enough. Fast.
+    MEDIANCUT,
+    /// refines MEDIANCUT by moving each color to the average of the colors nearest to it, up to
+    /// given number of times. Slower, usually closer to the original.
+    KMEANS(usize),
+}
+
+impl Default for Quantizer {
+    fn default() -> Self {
+        Self::KMEANS(8)
+    }
+}
+
+impl Matrix<Color> {
+    /// Reduces the matrix to at most colors colors, up to 255. Returns the generated palette and
+    /// the palette index of each color, starting at 1.
+    pub fn quantize(&self, colors: usize, quantizer: Quantizer) -> (Palette, Matrix<u8>) {
+        self.map(|color| Some(*color)).quantize(colors, quantizer)
+    }
+
+    /// palette index of the nearest looking color of palette for each color
+    pub fn map_to_palette(&self, palette: &Palette) -> Matrix<u8> {
+        self.map(|color| Some(*color)).map_to_palette(palette)
+    }
+}
+
+impl Matrix<Pixel> {
+    /// Reduces the matrix to at most colors colors, up to 255. Returns the generated palette and
+    /// the palette index of each pixel, 0 for transparent pixels.
+    pub fn quantize(&self, colors: usize, quantizer: Quantizer) -> (Palette, Matrix<u8>) {
+        let weighted = count(self.values.iter().flatten().copied());
+        let colors = colors.clamp(1, u8::MAX as usize);
+        let mut palette = median_cut(&weighted, colors);
+        if let Quantizer::KMEANS(iterations) = quantizer {
+            palette = k_means(&weighted, palette, iterations)
+        }
+        let palette = Palette::from_colors(palette);
+        let indices = self.map_to_palette(&palette);
+        (palette, indices)
+    }
+
+    /// palette index of the nearest looking color of palette for each pixel, 0 for transparent
+    /// pixels
+    pub fn map_to_palette(&self, palette: &Palette) -> Matrix<u8> {
+        let entries = palette
+            .colors()
+            .map(|(index, color)| (index, Oklab::from(color)))
+            .collect::<Vec<_>>();
+        self.map(|pixel| pixel.map_or(0, |color| nearest(&entries, Oklab::from(color))))
+    }
+}
+
+/// each distinct color with how many times it appears
+fn count(colors: impl Iterator<Item = Color>) -> Vec<(Color, usize)> {
+    let mut colors = colors.map(u32::from).collect::<Vec<_>>();
+    colors.sort_unstable();
+    colors
+        .chunk_by(|a, b| a == b)
+        .map(|same| (Color::from(same[0]), same.len()))
+        .collect::<Vec<_>>()
+}
+
+fn channels(color: Color) -> [u8; 3] {
+    [color.red, color.green, color.blue]
+}
+
+/// average of weighted colors
+fn average(colors: &[(Color, usize)]) -> Color {
+    let total = colors.iter().map(|(_, n)| n).sum::<usize>().max(1);
+    let channel = |i: usize| {
+        (colors
+            .iter()
+            .map(|(color, n)| channels(*color)[i] as usize * n)
+            .sum::<usize>() as f32
+            / total as f32)
+            .round() as u8
+    };
+    Color {
+        red: channel(0),
+        green: channel(1),
+        blue: channel(2),
+    }
+}
+
+/// Splits weighted colors into up to count boxes, each time halving the box with the widest
+/// channel at its median. Returns the average of each box.
+fn median_cut(colors: &[(Color, usize)], count: usize) -> Vec<Color> {
+    if colors.is_empty() {
+        return Vec::new();
+    }
+    let widest = |colors: &[(Color, usize)]| {
+        (0..3)
+            .map(|i| {
+                let values = colors.iter().map(|(color, _)| channels(*color)[i]);
+                let range = values.clone().max().unwrap_or(0) - values.min().unwrap_or(0);
+                (range, i)
+            })
+            .max()
+            .unwrap_or((0, 0))
+    };
+    let mut boxes = vec![colors.to_vec()];
+    while boxes.len() < count {
+        let Some((index, (_, channel))) = boxes
+            .iter()
+            .map(|colors| widest(colors))
+            .enumerate()
+            .filter(|(_, (range, _))| *range > 0)
+            .max_by_key(|(_, range)| *range)
+        else {
+            break;
+        };
+        let mut colors = boxes.swap_remove(index);
+        colors.sort_unstable_by_key(|(color, _)| channels(*color)[channel]);
+        let half = colors.iter().map(|(_, n)| n).sum::<usize>() / 2;
+        let mut seen = 0;
+        let split = colors
+            .iter()
+            .position(|(_, n)| {
+                seen += n;
+                seen > half
+            })
+            .unwrap_or(0)
+            .clamp(1, colors.len() - 1);
+        let upper = colors.split_off(split);
+        boxes.push(colors);
+        boxes.push(upper)
+    }
+    boxes
+        .iter()
+        .map(|colors| average(colors))
+        .collect::<Vec<_>>()
+}
+
+/// moves each of centers to the average of the weighted colors nearest to it, up to iterations
+/// times or until nothing moves
+fn k_means(colors: &[(Color, usize)], mut centers: Vec<Color>, iterations: usize) -> Vec<Color> {
+    let labs = colors
+        .iter()
+        .map(|(color, _)| Oklab::from(*color))
+        .collect::<Vec<_>>();
+    for _ in 0..iterations {
+        let entries = centers
+            .iter()
+            .enumerate()
+            .map(|(i, color)| (i as u8, Oklab::from(*color)))
+            .collect::<Vec<_>>();
+        let mut clusters = vec![Vec::new(); centers.len()];
+        colors
+            .iter()
+            .zip(&labs)
+            .for_each(|(color, lab)| clusters[nearest(&entries, *lab) as usize].push(*color));
+        let moved = clusters
+            .iter()
+            .zip(&centers)
+            .map(|(cluster, center)| {
+                if cluster.is_empty() {
+                    *center
+                } else {
+                    average(cluster)
+                }
+            })
+            .collect::<Vec<_>>();
+        if moved == centers {
+            break;
+        }
+        centers = moved
+    }
+    centers
+}
+
+/// index of the entry closest to color, 0 if there are none
+pub(crate) fn nearest(entries: &[(u8, Oklab)], color: Oklab) -> u8 {
+    entries
+        .iter()
+        .map(|(index, entry)| (*index, entry.distance_from(color)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(0, |(index, _)| index)
+}
@@ -0,0 +1,166 @@
+use super::{
+    dual_trait::Algebra,
+    matrix::Matrix,
+    transform::{Dimensions, Position, Rect},
+};
+
+/// Which neighbours of a value count as connected to it.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Connectivity {
+    /// left, right, up and down
+    #[default]
+    FOUR,
+    /// FOUR and the diagonals
+    EIGHT,
+}
+
+impl Connectivity {
+    /// offsets of connected neighbours
+    pub fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Self::FOUR => &[(1, 0), (-1, 0), (0, 1), (0, -1)],
+            Self::EIGHT => &[
+                (1, 0),
+                (-1, 0),
+                (0, 1),
+                (0, -1),
+                (1, 1),
+                (-1, 1),
+                (1, -1),
+                (-1, -1),
+            ],
+        }
+    }
+}
+
+/// Connected area of a matrix.
+#[derive(Clone, Default, Debug)]
+pub struct Region {
+    /// positions in the region, in the order they were found
+    pub cells: Vec<Position>,
+    /// smallest area covering every cell. A region wrapping around an edge spans the matrix.
+    pub bounds: Rect,
+}
+
+impl Region {
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    /// Labels each value with the index of its region, where a region is values equal to each
+    /// other and connected by connectivity. Regions connect across edges of wrapping matrices.
+    pub fn label(&self, connectivity: Connectivity) -> (Matrix<usize>, Vec<Region>)
+    where
+        T: Eq,
+    {
+        self.label_by(connectivity, |a, b| a == b)
+    }
+
+    /// like label, with neighbours connected when same(value, neighbour) is true. Useful for
+    /// types without Eq, such as tiles in a TileMap.
+    pub fn label_by(
+        &self,
+        connectivity: Connectivity,
+        same: impl Fn(&T, &T) -> bool,
+    ) -> (Matrix<usize>, Vec<Region>) {
+        let width = self.dimensions.width;
+        let position = |i: usize| Position::new((i % width) as isize, (i / width) as isize);
+        let mut labels: Vec<Option<usize>> = vec![None; self.values.len()];
+        let mut regions = Vec::new();
+        for start in 0..self.values.len() {
+            if labels[start].is_some() {
+                continue;
+            }
+            labels[start] = Some(regions.len());
+            let mut cells = Vec::new();
+            let mut stack = vec![start];
+            while let Some(index) = stack.pop() {
+                cells.push(position(index));
+                connectivity.offsets().iter().for_each(|(x, y)| {
+                    if let Some(neighbour) = self
+                        .some_bound(position(index).add(Position::new(*x, *y)))
+                        .filter(|n| labels[*n].is_none())
+                        .filter(|n| same(&self.values[index], &self.values[*n]))
+                    {
+                        labels[neighbour] = Some(regions.len());
+                        stack.push(neighbour)
+                    }
+                })
+            }
+            regions.push(Region {
+                bounds: bounds(&cells),
+                cells,
+            })
+        }
+        (
+            Matrix {
+                values: labels.into_iter().flatten().collect::<Vec<_>>(),
+                dimensions: self.dimensions,
+                wrapping: self.wrapping,
+            },
+            regions,
+        )
+    }
+
+    /// Lists the regions of equal, connected values.
+    pub fn regions(&self, connectivity: Connectivity) -> Vec<Region>
+    where
+        T: Eq,
+    {
+        self.label(connectivity).1
+    }
+
+    /// Lists the connected regions of values where predicate is true, ignoring the rest. For
+    /// example the opaque parts of a sprite with `|pixel| pixel.is_some()`.
+    pub fn regions_where(
+        &self,
+        connectivity: Connectivity,
+        predicate: impl Fn(&T) -> bool,
+    ) -> Vec<Region> {
+        self.label_by(connectivity, |a, b| predicate(a) && predicate(b))
+            .1
+            .into_iter()
+            .filter(|region| {
+                region
+                    .cells
+                    .first()
+                    .and_then(|position| self.get(*position))
+                    .is_some_and(&predicate)
+            })
+            .collect::<Vec<_>>()
+    }
+
+    /// matrix that is true for each value in region
+    pub fn mask(&self, region: &Region) -> Matrix<bool> {
+        let mut mask = Matrix {
+            values: vec![false; self.values.len()],
+            dimensions: self.dimensions,
+            wrapping: self.wrapping,
+        };
+        region
+            .cells
+            .iter()
+            .for_each(|position| mask.set(*position, true));
+        mask
+    }
+}
+
+/// smallest area covering every position
+fn bounds(cells: &[Position]) -> Rect {
+    let Some(first) = cells.first() else {
+        return Rect::default();
+    };
+    let (min, max) = cells.iter().fold((*first, *first), |(min, max), p| {
+        (
+            Position::new(min.x.min(p.x), min.y.min(p.y)),
+            Position::new(max.x.max(p.x), max.y.max(p.y)),
+        )
+    });
+    Rect::new(
+        min,
+        Dimensions::new((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize),
+    )
+}
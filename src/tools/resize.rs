@@ -0,0 +1,138 @@
+use super::{
+    dual_trait::Algebra,
+    matrix::Matrix,
+    transform::{Dimensions, Position, Rect},
+};
+
+/// Where content is placed along one axis when its area changes size.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Align {
+    /// left or top
+    #[default]
+    START,
+    CENTER,
+    /// right or bottom
+    END,
+}
+
+impl Align {
+    /// offset of content of length within an area of given length
+    fn offset(&self, length: usize, content: usize) -> isize {
+        let difference = length as isize - content as isize;
+        match self {
+            Self::START => 0,
+            Self::CENTER => difference / 2,
+            Self::END => difference,
+        }
+    }
+}
+
+/// Where existing content stays when a matrix is resized.
+#[derive(Clone, Copy, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Anchor {
+    pub horizontal: Align,
+    pub vertical: Align,
+}
+
+impl Anchor {
+    pub const TOP_LEFT: Self = Self::new(Align::START, Align::START);
+    pub const CENTER: Self = Self::new(Align::CENTER, Align::CENTER);
+    pub const BOTTOM_RIGHT: Self = Self::new(Align::END, Align::END);
+
+    pub const fn new(horizontal: Align, vertical: Align) -> Self {
+        Self {
+            horizontal,
+            vertical,
+        }
+    }
+
+    /// position of content with given dimensions inside an area of dimensions
+    pub fn offset(&self, dimensions: Dimensions, content: Dimensions) -> Position {
+        Position::new(
+            self.horizontal.offset(dimensions.width, content.width),
+            self.vertical.offset(dimensions.height, content.height),
+        )
+    }
+}
+
+/// Space added on each side of a matrix.
+#[derive(Clone, Copy, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Margins {
+    pub left: usize,
+    pub top: usize,
+    pub right: usize,
+    pub bottom: usize,
+}
+
+impl Margins {
+    pub fn new(left: usize, top: usize, right: usize, bottom: usize) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// same margin on every side
+    pub fn splat(margin: usize) -> Self {
+        Self::new(margin, margin, margin, margin)
+    }
+}
+
+impl<T: Default + Clone + Sync + Send> Matrix<T> {
+    /// Changes dimensions, keeping content placed by anchor. New values are fill and content
+    /// past the new bounds is cut off.
+    pub fn resize(&mut self, dimensions: Dimensions, anchor: Anchor, fill: T) {
+        let offset = anchor.offset(dimensions, self.dimensions);
+        self.place(dimensions, offset, fill)
+    }
+
+    /// keeps only the area of rect that is in bounds
+    pub fn crop(&mut self, rect: Rect) {
+        *self = Matrix {
+            wrapping: self.wrapping,
+            ..self.view(rect.position, rect.dimensions).to_matrix()
+        }
+    }
+
+    /// adds margins filled with fill around the matrix
+    pub fn pad(&mut self, margins: Margins, fill: T) {
+        let dimensions = Dimensions::new(
+            self.dimensions.width + margins.left + margins.right,
+            self.dimensions.height + margins.top + margins.bottom,
+        );
+        self.place(
+            dimensions,
+            Position::new(margins.left as isize, margins.top as isize),
+            fill,
+        )
+    }
+
+    /// tiles the matrix horizontally times and vertically times
+    pub fn repeat(&mut self, horizontally: usize, vertically: usize) {
+        let dimensions = self
+            .dimensions
+            .mul(Dimensions::new(horizontally, vertically));
+        *self = Matrix::from_fn(dimensions, self.wrapping, |position| {
+            self.get_wrap(position).clone()
+        })
+    }
+
+    /// Replaces the matrix by one of dimensions filled with fill, with the old one at position.
+    /// Drawn through a view so the old values are clipped even on wrapping matrices.
+    fn place(&mut self, dimensions: Dimensions, position: Position, fill: T) {
+        let mut matrix = Matrix {
+            values: vec![fill; dimensions.area()],
+            dimensions,
+            wrapping: self.wrapping,
+        };
+        matrix
+            .view_mut(Position::splat(0), dimensions)
+            .overlay(&*self, position);
+        *self = matrix
+    }
+}
@@ -0,0 +1,255 @@
+use super::{
+    color::{Color, Pixel},
+    dual_trait::Algebra,
+    matrix::Matrix,
+    transform::{Dimensions, Position, Rect},
+};
+
+/// How the final window matrix is enlarged before it is sent to the window.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum Upscale {
+    /// sends the matrix as is
+    #[default]
+    NONE,
+    /// repeats each value by given factor
+    NEAREST(usize),
+    SCALE2X,
+    SCALE3X,
+    HQ2X,
+}
+
+impl Upscale {
+    /// how many times larger each side of the matrix becomes
+    pub fn factor(&self) -> usize {
+        match self {
+            Self::NONE => 1,
+            Self::NEAREST(factor) => *factor,
+            Self::SCALE2X | Self::HQ2X => 2,
+            Self::SCALE3X => 3,
+        }
+    }
+
+    /// Area of the enlarged matrix that can change when area of a matrix of dimensions changes.
+    /// Scale2x, Scale3x and hq2x look at the neighbours of each value, so the area grows by a
+    /// value on each side first, spanning the whole axis where it crosses the edge of a wrapping
+    /// matrix.
+    pub fn scale_area(&self, area: Rect, dimensions: Dimensions, wrapping: bool) -> Rect {
+        let margin = match self {
+            Self::NONE | Self::NEAREST(_) => 0,
+            Self::SCALE2X | Self::SCALE3X | Self::HQ2X => 1,
+        };
+        let axis = |start: isize, length: usize, bound: usize| {
+            let (begin, end) = (start - margin, start + length as isize + margin);
+            if wrapping && (begin < 0 || end > bound as isize) {
+                (0, bound)
+            } else {
+                let (begin, end) = (begin.max(0), end.min(bound as isize));
+                (begin, (end - begin).max(0) as usize)
+            }
+        };
+        let (x, width) = axis(area.position.x, area.dimensions.width, dimensions.width);
+        let (y, height) = axis(area.position.y, area.dimensions.height, dimensions.height);
+        let factor = self.factor();
+        Rect::new(
+            Position::new(x, y).mul(Position::splat(factor as isize)),
+            Dimensions::new(width, height).mul(Dimensions::splat(factor)),
+        )
+    }
+
+    /// returns the enlarged matrix, or None if it is unchanged
+    pub fn apply(&self, matrix: &Matrix<Color>) -> Option<Matrix<Color>> {
+        match self {
+            Self::NONE | Self::NEAREST(1) => None,
+            Self::NEAREST(factor) => Some(matrix.scale_nearest(*factor)),
+            Self::SCALE2X => Some(matrix.scale2x()),
+            Self::SCALE3X => Some(matrix.scale3x()),
+            Self::HQ2X => Some(matrix.hq2x()),
+        }
+    }
+}
+
+impl<T: Default + Clone + Sync + Send> Matrix<T> {
+    /// enlarges the matrix by repeating each value factor times on both axes
+    pub fn scale_nearest(&self, factor: usize) -> Self {
+        let mut matrix = Matrix::new(
+            self.dimensions.mul(Dimensions::splat(factor)),
+            self.wrapping,
+        );
+        matrix.fill_with(|position| {
+            self.get_wrap(position.div(Position::splat(factor as isize)))
+                .clone()
+        });
+        matrix
+    }
+
+    /// shrinks the matrix by keeping the top left value of each factor by factor block
+    pub fn downscale_nearest(&self, factor: usize) -> Self {
+        let mut matrix = Matrix::new(
+            self.dimensions.div(Dimensions::splat(factor)),
+            self.wrapping,
+        );
+        matrix.fill_with(|position| {
+            self.get_wrap(position.mul(Position::splat(factor as isize)))
+                .clone()
+        });
+        matrix
+    }
+
+    /// doubles the matrix in size, rounding off diagonal edges (Scale2x/EPX)
+    pub fn scale2x(&self) -> Self
+    where
+        T: PartialEq,
+    {
+        let mut matrix = Matrix::new(self.dimensions.mul(Dimensions::splat(2)), self.wrapping);
+        matrix.fill_with(|position| {
+            let source = position.div(Position::splat(2));
+            let (x, y) = (position.x % 2 * 2 - 1, position.y % 2 * 2 - 1);
+            let vertical = self.neighbour(source, 0, y);
+            let horizontal = self.neighbour(source, x, 0);
+            if horizontal == vertical
+                && horizontal != self.neighbour(source, 0, -y)
+                && vertical != self.neighbour(source, -x, 0)
+            {
+                vertical.clone()
+            } else {
+                self.neighbour(source, 0, 0).clone()
+            }
+        });
+        matrix
+    }
+
+    /// triples the matrix in size, rounding off diagonal edges (Scale3x)
+    pub fn scale3x(&self) -> Self
+    where
+        T: PartialEq,
+    {
+        let mut matrix = Matrix::new(self.dimensions.mul(Dimensions::splat(3)), self.wrapping);
+        matrix.fill_with(|position| {
+            let source = position.div(Position::splat(3));
+            let at = |x, y| self.neighbour(source, x, y);
+            let [a, b, c, d, e, f, g, h, i] = [
+                at(-1, -1),
+                at(0, -1),
+                at(1, -1),
+                at(-1, 0),
+                at(0, 0),
+                at(1, 0),
+                at(-1, 1),
+                at(0, 1),
+                at(1, 1),
+            ];
+            let (top_left, top_right) = (d == b && b != f && d != h, b == f && b != d && f != h);
+            let (bottom_left, bottom_right) =
+                (d == h && d != b && h != f, h == f && d != h && b != f);
+            match (position.x % 3, position.y % 3) {
+                (0, 0) if top_left => d,
+                (1, 0) if (top_left && e != c) || (top_right && e != a) => b,
+                (2, 0) if top_right => f,
+                (0, 1) if (top_left && e != g) || (bottom_left && e != a) => d,
+                (2, 1) if (top_right && e != i) || (bottom_right && e != c) => f,
+                (0, 2) if bottom_left => d,
+                (1, 2) if (bottom_left && e != i) || (bottom_right && e != g) => h,
+                (2, 2) if bottom_right => f,
+                _ => e,
+            }
+            .clone()
+        });
+        matrix
+    }
+
+    /// returns the value at position moved by (x, y). Past the edges, the value wraps if the
+    /// matrix is wrapping and repeats the edge value otherwise.
+    fn neighbour(&self, position: Position, x: isize, y: isize) -> &T {
+        let position = position.add(Position::new(x, y));
+        if self.wrapping {
+            self.get_wrap(position)
+        } else {
+            self.get_wrap(Position::new(
+                position.x.clamp(0, self.dimensions.width as isize - 1),
+                position.y.clamp(0, self.dimensions.height as isize - 1),
+            ))
+        }
+    }
+
+    /// doubles the matrix in size, blending along edges between values that are alike (hq2x
+    /// style). similar decides when two values are alike and corner makes an outer corner from
+    /// the center value and its two edge neighbours.
+    fn hqx(
+        &self,
+        similar: impl Fn(&T, &T) -> bool + Sync,
+        corner: impl Fn(&T, &T, &T, bool) -> T + Sync,
+    ) -> Self {
+        let mut matrix = Matrix::new(self.dimensions.mul(Dimensions::splat(2)), self.wrapping);
+        matrix.fill_with(|position| {
+            let source = position.div(Position::splat(2));
+            let (x, y) = (position.x % 2 * 2 - 1, position.y % 2 * 2 - 1);
+            let center = self.neighbour(source, 0, 0);
+            let vertical = self.neighbour(source, 0, y);
+            let horizontal = self.neighbour(source, x, 0);
+            let diagonal = self.neighbour(source, x, y);
+            if similar(vertical, horizontal) && !similar(center, vertical) {
+                corner(center, vertical, horizontal, similar(diagonal, vertical))
+            } else {
+                center.clone()
+            }
+        });
+        matrix
+    }
+}
+
+impl Matrix<Color> {
+    /// doubles the matrix in size, smoothly blending diagonal edges (hq2x style)
+    pub fn hq2x(&self) -> Self {
+        self.hqx(similar, blend_corner)
+    }
+}
+
+impl Matrix<Pixel> {
+    /// doubles the matrix in size, smoothly blending diagonal edges (hq2x style). Edges against
+    /// transparent pixels are rounded off like Scale2x instead of blended.
+    pub fn hq2x(&self) -> Self {
+        self.hqx(
+            |a, b| match (a, b) {
+                (Some(a), Some(b)) => similar(a, b),
+                (a, b) => a.is_none() && b.is_none(),
+            },
+            |center, vertical, horizontal, solid| match (center, vertical, horizontal) {
+                (Some(center), Some(vertical), Some(horizontal)) => {
+                    Some(blend_corner(center, vertical, horizontal, solid))
+                }
+                _ => *vertical,
+            },
+        )
+    }
+}
+
+/// compares colors in YUV with the thresholds used by hqx
+fn similar(a: &Color, b: &Color) -> bool {
+    let yuv = |c: &Color| {
+        let (r, g, b) = (c.red as f32, c.green as f32, c.blue as f32);
+        (
+            0.299 * r + 0.587 * g + 0.114 * b,
+            -0.169 * r - 0.331 * g + 0.5 * b,
+            0.5 * r - 0.419 * g - 0.081 * b,
+        )
+    };
+    let (a, b) = (yuv(a), yuv(b));
+    (a.0 - b.0).abs() <= 48. && (a.1 - b.1).abs() <= 7. && (a.2 - b.2).abs() <= 6.
+}
+
+/// blends the outer corner of center towards the edge it sits on. A solid corner, where the
+/// diagonal neighbour matches the edge, is pulled further towards the edge.
+fn blend_corner(center: &Color, vertical: &Color, horizontal: &Color, solid: bool) -> Color {
+    let (center_weight, edge_weight) = if solid { (2, 3) } else { (2, 1) };
+    let total = center_weight + edge_weight * 2;
+    let channel = |c: fn(&Color) -> u8| {
+        ((c(center) as u32 * center_weight
+            + (c(vertical) as u32 + c(horizontal) as u32) * edge_weight)
+            / total) as u8
+    };
+    Color {
+        red: channel(|c| c.red),
+        green: channel(|c| c.green),
+        blue: channel(|c| c.blue),
+    }
+}
@@ -1,5 +1,7 @@
 use std::time::{Duration, Instant};
 
+/// Timer keeps track of time since it was last reset. With the serde feature it is saved as its
+/// duration and remaining time, and resumes from there when loaded.
 pub struct Timer {
     pub duration: Duration,
     elapsed_time: Duration,
@@ -31,3 +33,39 @@ impl Timer {
         self.instant = Instant::now();
     }
 }
+
+/// what a Timer is saved as, since an Instant means nothing once loaded
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SavedTimer {
+    duration: Duration,
+    remaining: Duration,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Timer {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SavedTimer {
+            duration: self.duration,
+            remaining: self.duration.saturating_sub(self.instant.elapsed()),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Timer {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let SavedTimer {
+            duration,
+            remaining,
+        } = SavedTimer::deserialize(deserializer)?;
+        let elapsed_time = duration.saturating_sub(remaining);
+        let now = Instant::now();
+        Ok(Self {
+            duration,
+            elapsed_time,
+            instant: now.checked_sub(elapsed_time).unwrap_or(now),
+        })
+    }
+}
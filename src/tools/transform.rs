@@ -1,12 +1,40 @@
-use super::dual_trait::Algebra;
+use super::{dual_trait::Algebra, sampling::Sampling};
 
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Transform {
     pub position: Position,
-    pub rotation: Rotation,
+    pub orientation: Orientation,
+    /// clockwise rotation in radians, applied around the center after orientation
+    pub angle: f32,
+    /// size multiplier. 1.0 is the original size
+    pub scale: f32,
+    /// how the tile is sampled when angle or scale are used
+    pub sampling: Sampling,
 }
 
-#[derive(Clone, Copy, Default)]
+impl Default for Transform {
+    fn default() -> Self {
+        Self {
+            position: Position::default(),
+            orientation: Orientation::default(),
+            angle: 0.,
+            scale: 1.,
+            sampling: Sampling::default(),
+        }
+    }
+}
+
+impl Transform {
+    /// true if angle or scale require the tile to be resampled
+    pub fn is_resampled(&self) -> bool {
+        self.angle != 0. || self.scale != 1.
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Rotation {
     #[default]
     UP,
@@ -15,14 +43,121 @@ pub enum Rotation {
     RIGHT,
 }
 
+impl Rotation {
+    /// number of clockwise quarter turns from UP
+    pub fn quarter_turns(&self) -> usize {
+        match self {
+            Self::UP => 0,
+            Self::RIGHT => 1,
+            Self::DOWN => 2,
+            Self::LEFT => 3,
+        }
+    }
+
+    pub fn from_quarter_turns(turns: usize) -> Self {
+        match turns % 4 {
+            0 => Self::UP,
+            1 => Self::RIGHT,
+            2 => Self::DOWN,
+            _ => Self::LEFT,
+        }
+    }
+}
+
+/// One of the eight ways a matrix can be turned over onto itself: a rotation, optionally
+/// preceded by a horizontal mirror.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Orientation {
+    pub rotation: Rotation,
+    /// if true, the matrix is mirrored horizontally (x = 0) before it is rotated
+    pub mirrored: bool,
+}
+
+impl Orientation {
+    pub const IDENTITY: Self = Self::new(Rotation::UP, false);
+    /// mirrors on the x = 0 axis, which makes a sprite face the other way
+    pub const REFLECT_HORIZONTAL: Self = Self::new(Rotation::UP, true);
+    /// mirrors on the y = 0 axis
+    pub const REFLECT_VERTICAL: Self = Self::new(Rotation::DOWN, true);
+    /// mirrors on the y = x axis
+    pub const REFLECT_DIAGONAL: Self = Self::new(Rotation::LEFT, true);
+    /// mirrors on the y = -x axis
+    pub const REFLECT_NEGATIVE_DIAGONAL: Self = Self::new(Rotation::RIGHT, true);
+
+    pub const fn new(rotation: Rotation, mirrored: bool) -> Self {
+        Self { rotation, mirrored }
+    }
+
+    /// all eight orientations, rotations first
+    pub fn all() -> [Self; 8] {
+        [
+            Self::IDENTITY,
+            Self::new(Rotation::RIGHT, false),
+            Self::new(Rotation::DOWN, false),
+            Self::new(Rotation::LEFT, false),
+            Self::REFLECT_HORIZONTAL,
+            Self::REFLECT_NEGATIVE_DIAGONAL,
+            Self::REFLECT_VERTICAL,
+            Self::REFLECT_DIAGONAL,
+        ]
+    }
+
+    /// returns the orientation of applying self first, then other
+    pub fn then(&self, other: Self) -> Self {
+        let turns = if other.mirrored {
+            4 + other.rotation.quarter_turns() - self.rotation.quarter_turns()
+        } else {
+            other.rotation.quarter_turns() + self.rotation.quarter_turns()
+        };
+        Self::new(
+            Rotation::from_quarter_turns(turns),
+            self.mirrored != other.mirrored,
+        )
+    }
+
+    /// returns the orientation that undoes self
+    pub fn inverse(&self) -> Self {
+        if self.mirrored {
+            *self
+        } else {
+            Self::new(
+                Rotation::from_quarter_turns(4 - self.rotation.quarter_turns()),
+                false,
+            )
+        }
+    }
+
+    /// true if width and height trade places under this orientation
+    pub fn swaps_dimensions(&self) -> bool {
+        self.rotation.quarter_turns() % 2 == 1
+    }
+
+    /// returns the dimensions of a matrix with given dimensions after being oriented
+    pub fn dimensions(&self, mut dimensions: Dimensions) -> Dimensions {
+        if self.swaps_dimensions() {
+            dimensions.swap()
+        }
+        dimensions
+    }
+}
+
+impl From<Rotation> for Orientation {
+    fn from(value: Rotation) -> Self {
+        Self::new(value, false)
+    }
+}
+
+/// Signed position. Values may be negative, such as an entity partly off the left or top edge.
 #[derive(Clone, Copy, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
-    pub x: usize,
-    pub y: usize,
+    pub x: isize,
+    pub y: isize,
 }
 
 impl Algebra for Position {
-    type Item = usize;
+    type Item = isize;
 
     fn new(first: Self::Item, last: Self::Item) -> Self {
         Self { x: first, y: last }
@@ -42,6 +177,7 @@ impl Algebra for Position {
 }
 
 #[derive(Clone, Copy, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Dimensions {
     pub width: usize,
     pub height: usize,
@@ -73,6 +209,32 @@ impl Algebra for Dimensions {
     }
 }
 
+/// Area with width and height starting at (x, y).
+#[derive(Clone, Copy, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect {
+    pub position: Position,
+    pub dimensions: Dimensions,
+}
+
+impl Rect {
+    pub fn new(position: Position, dimensions: Dimensions) -> Self {
+        Self {
+            position,
+            dimensions,
+        }
+    }
+}
+
+impl From<Dimensions> for Position {
+    fn from(value: Dimensions) -> Self {
+        Self {
+            x: value.width as isize,
+            y: value.height as isize,
+        }
+    }
+}
+
 impl Dimensions {
     pub fn area(&self) -> usize {
         self.mul_self()
@@ -0,0 +1,283 @@
+use std::ops::Range;
+
+use super::{
+    dual_trait::Algebra,
+    matrix::{intersect, Matrix},
+    transform::{Dimensions, Position, Rect},
+};
+
+/// MatrixView borrows a rectangular area of a Matrix without copying it.
+#[derive(Debug)]
+pub struct MatrixView<'a, T> {
+    /// values from the first value of the area to its last, including those between rows
+    values: &'a [T],
+    /// width of the matrix the values are borrowed from
+    stride: usize,
+    dimensions: Dimensions,
+}
+
+/// MatrixViewMut mutably borrows a rectangular area of a Matrix without copying it.
+#[derive(Debug)]
+pub struct MatrixViewMut<'a, T> {
+    /// values from the first value of the area to its last, including those between rows
+    values: &'a mut [T],
+    /// width of the matrix the values are borrowed from
+    stride: usize,
+    dimensions: Dimensions,
+}
+
+/// returns the range of values covering an area that is in bounds
+fn span(stride: usize, area: Rect) -> Range<usize> {
+    let Rect {
+        position,
+        dimensions,
+    } = area;
+    if dimensions.area() == 0 {
+        return 0..0;
+    }
+    let first = position.x as usize + position.y as usize * stride;
+    first..first + (dimensions.height - 1) * stride + dimensions.width
+}
+
+/// returns the index of position in an area of dimensions, if it is inside
+fn index(stride: usize, dimensions: Dimensions, position: Position) -> Option<usize> {
+    (position.x >= 0
+        && position.y >= 0
+        && (position.x as usize) < dimensions.width
+        && (position.y as usize) < dimensions.height)
+        .then(|| position.x as usize + position.y as usize * stride)
+}
+
+impl<T> Clone for MatrixView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for MatrixView<'_, T> {}
+
+impl<'a, T> MatrixView<'a, T> {
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    pub fn get(&self, position: Position) -> Option<&'a T> {
+        index(self.stride, self.dimensions, position).map(|i| &self.values[i])
+    }
+
+    /// lists each row of the view as a slice
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+        let width = self.dimensions.width;
+        self.values
+            .chunks(self.stride.max(1))
+            .take(self.dimensions.height)
+            .map(move |row| &row[..width])
+    }
+
+    /// lists values row by row
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> {
+        self.rows().flatten()
+    }
+
+    pub fn enumerate(&self) -> impl Iterator<Item = (Position, &'a T)> {
+        self.rows().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, t)| (Position::new(x as isize, y as isize), t))
+        })
+    }
+
+    /// borrows the area with width and height starting at (x, y) of this view. The area is
+    /// limited to what is in bounds.
+    pub fn view(&self, position: Position, dimensions: Dimensions) -> MatrixView<'a, T> {
+        let area = intersect(self.dimensions, position, dimensions);
+        MatrixView {
+            values: &self.values[span(self.stride, area)],
+            stride: self.stride,
+            dimensions: area.dimensions,
+        }
+    }
+}
+
+impl<T: Default + Clone + Sync + Send> MatrixView<'_, T> {
+    /// copies the view into a new matrix
+    pub fn to_matrix(&self) -> Matrix<T> {
+        Matrix {
+            values: self.iter().cloned().collect::<Vec<_>>(),
+            dimensions: self.dimensions,
+            wrapping: false,
+        }
+    }
+}
+
+impl<'a, T: Clone> From<&'a Matrix<T>> for MatrixView<'a, T> {
+    fn from(value: &'a Matrix<T>) -> Self {
+        Self {
+            values: &value.values,
+            stride: value.dimensions.width,
+            dimensions: value.dimensions,
+        }
+    }
+}
+
+impl<'a, T> MatrixViewMut<'a, T> {
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        index(self.stride, self.dimensions, position).map(|i| &self.values[i])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        index(self.stride, self.dimensions, position).map(|i| &mut self.values[i])
+    }
+
+    pub fn set(&mut self, position: Position, value: T) {
+        if let Some(t) = self.get_mut(position) {
+            *t = value
+        }
+    }
+
+    /// reborrows as an immutable view
+    pub fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView {
+            values: self.values,
+            stride: self.stride,
+            dimensions: self.dimensions,
+        }
+    }
+
+    /// lists each row of the view as a slice
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.as_view().rows()
+    }
+
+    /// lists each row of the view as a mutable slice
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        let width = self.dimensions.width;
+        self.values
+            .chunks_mut(self.stride.max(1))
+            .take(self.dimensions.height)
+            .map(move |row| &mut row[..width])
+    }
+
+    /// lists values row by row
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.as_view().iter()
+    }
+
+    /// lists mutable values row by row
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.rows_mut().flatten()
+    }
+
+    pub fn enumerate_mut(&mut self) -> impl Iterator<Item = (Position, &mut T)> {
+        self.rows_mut().enumerate().flat_map(|(y, row)| {
+            row.iter_mut()
+                .enumerate()
+                .map(move |(x, t)| (Position::new(x as isize, y as isize), t))
+        })
+    }
+
+    /// mutably borrows the area with width and height starting at (x, y) of this view. The area
+    /// is limited to what is in bounds.
+    pub fn view_mut(&mut self, position: Position, dimensions: Dimensions) -> MatrixViewMut<'_, T> {
+        let area = intersect(self.dimensions, position, dimensions);
+        MatrixViewMut {
+            values: &mut self.values[span(self.stride, area)],
+            stride: self.stride,
+            dimensions: area.dimensions,
+        }
+    }
+
+    /// Overlays view with other given view starting at position (x, y). Values landing outside
+    /// the view are clipped.
+    pub fn overlay<'b>(&mut self, view: impl Into<MatrixView<'b, T>>, position: Position)
+    where
+        T: Clone + 'b,
+    {
+        self.blit(view.into(), position, |t, u| t.clone_from(u))
+    }
+
+    /// Overlays view only with Some(T) value.
+    pub fn transparent_overlay<'b>(
+        &mut self,
+        view: impl Into<MatrixView<'b, Option<T>>>,
+        position: Position,
+    ) where
+        T: Clone + 'b,
+    {
+        self.blit(view.into(), position, |t, u| {
+            if let Some(item) = u {
+                t.clone_from(item)
+            }
+        })
+    }
+
+    fn blit<U>(&mut self, view: MatrixView<'_, U>, position: Position, func: impl Fn(&mut T, &U)) {
+        let Rect {
+            position: start,
+            dimensions: visible,
+        } = intersect(self.dimensions, position, view.dimensions);
+        let skip = start.sub(position);
+        let (x, skip_x) = (start.x as usize, skip.x as usize);
+        self.rows_mut()
+            .skip(start.y as usize)
+            .take(visible.height)
+            .zip(view.rows().skip(skip.y as usize))
+            .for_each(|(row, source)| {
+                row[x..x + visible.width]
+                    .iter_mut()
+                    .zip(&source[skip_x..])
+                    .for_each(|(t, u)| func(t, u))
+            })
+    }
+}
+
+impl<'a, T: Clone> From<&'a mut Matrix<T>> for MatrixViewMut<'a, T> {
+    fn from(value: &'a mut Matrix<T>) -> Self {
+        Self {
+            stride: value.dimensions.width,
+            dimensions: value.dimensions,
+            values: &mut value.values,
+        }
+    }
+}
+
+impl<T: Default + Clone + Sync + Send> Matrix<T> {
+    /// borrows the area with width and height starting at (x, y) without copying. The area is
+    /// limited to what is in bounds.
+    pub fn view(&self, position: Position, dimensions: Dimensions) -> MatrixView<'_, T> {
+        MatrixView::from(self).view(position, dimensions)
+    }
+
+    /// mutably borrows the area with width and height starting at (x, y) without copying. The
+    /// area is limited to what is in bounds.
+    pub fn view_mut(&mut self, position: Position, dimensions: Dimensions) -> MatrixViewMut<'_, T> {
+        let area = intersect(self.dimensions, position, dimensions);
+        MatrixViewMut {
+            values: &mut self.values[span(self.dimensions.width, area)],
+            stride: self.dimensions.width,
+            dimensions: area.dimensions,
+        }
+    }
+
+    /// returns views of the matrix split into given number of areas, row by row. Like
+    /// subdivide_matrix, but without copying.
+    pub fn subdivide_views(
+        &self,
+        subdivision_quantities: Dimensions,
+    ) -> impl Iterator<Item = MatrixView<'_, T>> {
+        let length_dimensions = self.dimensions.div(subdivision_quantities);
+        (0..subdivision_quantities.area()).map(move |i| {
+            self.view(
+                Position::new(
+                    (i % subdivision_quantities.width * length_dimensions.width) as isize,
+                    (i / subdivision_quantities.width * length_dimensions.height) as isize,
+                ),
+                length_dimensions,
+            )
+        })
+    }
+}
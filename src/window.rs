@@ -1,22 +1,43 @@
 use crate::{
     entity::entity::Entity,
-    graphics::tile::Tile,
+    graphics::{effect::PostEffect, tile::Tile},
     tools::{
-        color::Color,
+        blend::BlendMode,
+        color::{Color, Pixel, Rgba},
+        dual_trait::Algebra,
         matrix::Matrix,
-        transform::{Dimensions, Transform},
+        parallel,
+        scaling::Upscale,
+        transform::{Dimensions, Position, Rect, Transform},
     },
 };
 use minifb::{Error, Scale, ScaleMode, Window, WindowOptions};
 
 /// WindowController holds the main interaction between the actual matrix holding the tiles and the
 /// minifb Window.
-
 pub struct WindowController {
     /// Where colors to be displayed are stored.
     pub matrix: Matrix<Color>,
     /// The actual values that are transfered to the window
     buffer: Vec<u32>,
+    /// dimensions of buffer, which are larger than those of matrix when upscaled
+    buffer_dimensions: Dimensions,
+    /// How the matrix is enlarged before it is transfered to the window. Done on top of the
+    /// Window scale.
+    pub upscale: Upscale,
+    /// last matrix sent to the window, compared against to find dirty areas
+    previous: Matrix<Color>,
+    /// upscale the buffer was last enlarged with. Only dirty areas are converted while it
+    /// matches upscale.
+    buffer_upscale: Upscale,
+    /// areas of the matrix that changed in the last update
+    dirty: Vec<Rect>,
+    /// Light reaching each position of the matrix, such as from Lighting::light_map. The matrix
+    /// and entities are multiplied by it once composited.
+    pub light_map: Option<Matrix<Color>>,
+    /// Effects applied in order to each frame after it is composited and before it is shown.
+    /// Disabled effects are skipped.
+    pub effects: Vec<PostEffect>,
     /// Provided by minifb, the device that displays the tiles.
     pub window: Window,
 }
@@ -44,6 +65,13 @@ impl WindowController {
         Self {
             matrix: Matrix::new(dimensions, wrapping),
             buffer: vec![0; dimensions.area()],
+            buffer_dimensions: dimensions,
+            upscale: Upscale::NONE,
+            previous: Matrix::default(),
+            buffer_upscale: Upscale::NONE,
+            dirty: Vec::new(),
+            light_map: None,
+            effects: Vec::new(),
             window,
         }
     }
@@ -52,31 +80,176 @@ impl WindowController {
     pub fn update(&mut self) -> Result<(), Error> {
         self.window.update_with_buffer(
             &self.buffer,
-            self.matrix.dimensions.width,
-            self.matrix.dimensions.height,
+            self.buffer_dimensions.width,
+            self.buffer_dimensions.height,
         )
     }
 
+    /// Updates Window buffer with colors laid out like matrix.
     pub fn update_buffer(&mut self, buffer: impl Iterator<Item = Color>) -> Result<(), Error> {
-        self.buffer = buffer.map(|c| u32::from(c)).collect::<Vec<_>>();
+        self.update_matrix(&Matrix {
+            values: buffer.collect::<Vec<_>>(),
+            dimensions: self.matrix.dimensions,
+            wrapping: self.matrix.wrapping,
+        })
+    }
+
+    /// Updates Window buffer with given matrix, after effects and enlarged according to upscale.
+    /// Only the areas that changed since the last update are converted, enlarged to match.
+    pub fn update_matrix(&mut self, matrix: &Matrix<Color>) -> Result<(), Error> {
+        let processed;
+        let matrix = if self.effects.iter().any(|e| e.enabled) {
+            let mut frame = matrix.clone();
+            self.effects
+                .iter_mut()
+                .filter(|e| e.enabled)
+                .for_each(|e| e.effect.apply(&mut frame));
+            processed = frame;
+            &processed
+        } else {
+            matrix
+        };
+        self.dirty = matrix.diff(&self.previous);
+        let upscaled = self.upscale.apply(matrix);
+        let scaled = upscaled.as_ref().unwrap_or(matrix);
+        if self.buffer_upscale == self.upscale
+            && self.buffer_dimensions.eq(scaled.dimensions)
+            && self.buffer.len() == scaled.values.len()
+        {
+            let width = scaled.dimensions.width;
+            self.dirty
+                .iter()
+                .map(|rect| {
+                    self.upscale
+                        .scale_area(*rect, matrix.dimensions, matrix.wrapping)
+                })
+                .for_each(|rect| {
+                    (0..rect.dimensions.height).for_each(|y| {
+                        let start =
+                            rect.position.x as usize + (rect.position.y as usize + y) * width;
+                        let range = start..start + rect.dimensions.width;
+                        self.buffer[range.clone()]
+                            .iter_mut()
+                            .zip(&scaled.values[range])
+                            .for_each(|(u, c)| *u = u32::from(*c))
+                    })
+                })
+        } else {
+            self.buffer = parallel::map(&scaled.values, |c| u32::from(*c));
+            self.buffer_dimensions = scaled.dimensions;
+            self.buffer_upscale = self.upscale;
+        }
+        self.previous.values.clone_from(&matrix.values);
+        self.previous.dimensions = matrix.dimensions;
         self.update()
     }
 
-    /// Updates window buffer each frame called and adds entities.
+    /// Areas of the matrix that changed in the last update, in matrix positions. Empty if
+    /// nothing changed.
+    pub fn dirty_rects(&self) -> &[Rect] {
+        &self.dirty
+    }
+
+    /// Updates window buffer each frame called and adds entities, lit by light_map if set.
+    /// Entities partly outside the matrix are clipped.
     pub fn update_with_entities(&mut self, entities: &mut [impl Entity]) -> Result<(), Error> {
         let mut matrix_with_entities = self.matrix.clone();
-        entities.sort_by(|a, b| a.get_order().cmp(&b.get_order()));
+        entities.sort_by(|a, b| a.get_order().cmp(b.get_order()));
 
         entities.iter_mut().for_each(|e| {
-            let (Transform { position, rotation }, tile) = e.get_position_matrix();
-            let tile_matrix = tile.get_matrix();
-            matrix_with_entities.transparent_overlay_iter(
-                tile_matrix.iter_rotate(*rotation).copied(),
-                *position,
-                tile_matrix.dimensions,
+            let (transform, tile) = e.get_position_matrix();
+            let (mode, opacity) = (e.get_blend_mode(), e.get_opacity());
+            if let Some(rgba) = tile.get_rgba() {
+                let (values, position, dimensions) = transformed(rgba, transform);
+                matrix_with_entities.blend_overlay_iter(
+                    values.into_iter().map(|rgba| rgba.fade(opacity)),
+                    position,
+                    dimensions,
+                    mode,
+                );
+                return;
+            }
+            let repainted;
+            let tile_matrix = match (e.get_palette(), tile.get_indices()) {
+                (Some(palette), Some(indices)) => {
+                    repainted = palette.resolve_matrix(indices);
+                    &repainted
+                }
+                _ => tile.get_matrix(),
+            };
+            let (values, position, dimensions) = transformed(tile_matrix, transform);
+            draw_pixels(
+                &mut matrix_with_entities,
+                values.into_iter(),
+                position,
+                dimensions,
+                mode,
+                opacity,
             )
         });
 
-        self.update_buffer(matrix_with_entities.values.iter().copied())
+        if let Some(light_map) = &self.light_map {
+            matrix_with_entities.blend_overlay_iter(
+                light_map.values.iter().map(|color| Rgba::from(*color)),
+                Position::splat(0),
+                light_map.dimensions,
+                BlendMode::MULTIPLY,
+            )
+        }
+
+        self.update_matrix(&matrix_with_entities)
+    }
+}
+
+/// Orients, rotates and scales the values of a tile by transform, returning them with where
+/// and how large they are drawn.
+fn transformed<T: Default + Clone + PartialEq + Sync + Send>(
+    matrix: &Matrix<T>,
+    transform: &Transform,
+) -> (Vec<T>, Position, Dimensions) {
+    let Transform {
+        position,
+        orientation,
+        angle,
+        scale,
+        sampling,
+    } = *transform;
+    let dimensions = orientation.dimensions(matrix.dimensions);
+    let values = matrix.iter_rotate(orientation).cloned().collect::<Vec<_>>();
+    if !transform.is_resampled() {
+        return (values, position, dimensions);
+    }
+    let sampled = Matrix {
+        values,
+        dimensions,
+        wrapping: false,
+    }
+    .sample(angle, scale, sampling, T::default());
+    // keeps the sprite centered on where it would be unrotated and unscaled
+    let offset = Position::from(dimensions)
+        .sub(Position::from(sampled.dimensions))
+        .div(Position::splat(2));
+    (sampled.values, position.add(offset), sampled.dimensions)
+}
+
+/// Draws pixels of an entity over matrix. Opaque entities in NORMAL mode replace what is below,
+/// others are blended.
+fn draw_pixels(
+    matrix: &mut Matrix<Color>,
+    pixels: impl Iterator<Item = Pixel>,
+    position: Position,
+    dimensions: Dimensions,
+    mode: BlendMode,
+    opacity: f32,
+) {
+    if mode == BlendMode::NORMAL && opacity >= 1. {
+        matrix.transparent_overlay_iter(pixels, position, dimensions)
+    } else {
+        matrix.blend_overlay_iter(
+            pixels.map(|pixel| Rgba::from(pixel).fade(opacity)),
+            position,
+            dimensions,
+            mode,
+        )
     }
 }
@@ -0,0 +1,115 @@
+mod common;
+
+use common::{grid, sample};
+use minifb_tile_base::tools::{
+    dual_trait::Algebra,
+    matrix::Matrix,
+    transform::{Dimensions, Position},
+};
+
+/// 4x3 of zeros that doesn't wrap
+fn blank() -> Matrix<u8> {
+    Matrix::new(Dimensions::new(4, 3), false)
+}
+
+/// blank with sample overlaid at (x, y), by matrix, by iterator and through a view
+fn overlaid(x: isize, y: isize) -> Matrix<u8> {
+    let position = Position::new(x, y);
+    let mut matrix = blank();
+    matrix.overlay(&sample(), position);
+
+    let mut iterated = blank();
+    iterated.overlay_iter(sample().values.iter(), position, sample().dimensions);
+    assert_eq!(iterated, matrix);
+
+    let mut viewed = blank();
+    viewed
+        .view_mut(Position::splat(0), Dimensions::new(4, 3))
+        .overlay(&sample(), position);
+    assert_eq!(viewed, matrix);
+    matrix
+}
+
+#[test]
+fn overlay_top_left() {
+    assert_eq!(
+        overlaid(-1, -1),
+        grid([[5, 6, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0]], false)
+    );
+}
+
+#[test]
+fn overlay_bottom_right() {
+    assert_eq!(
+        overlaid(3, 2),
+        grid([[0, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 1]], false)
+    );
+}
+
+#[test]
+fn overlay_inside() {
+    assert_eq!(
+        overlaid(1, 1),
+        grid([[0, 0, 0, 0], [0, 1, 2, 3], [0, 4, 5, 6]], false)
+    );
+}
+
+#[test]
+fn overlay_off_screen() {
+    [(-3, 0), (0, -2), (4, 0), (0, 3), (-10, 10)]
+        .iter()
+        .for_each(|(x, y)| assert_eq!(overlaid(*x, *y), blank(), "({x}, {y})"));
+}
+
+#[test]
+fn transparent_overlay_clips() {
+    let mut matrix = blank();
+    let sprite = sample().map(|value| (value % 2 == 0).then_some(*value));
+    matrix.transparent_overlay(&sprite, Position::new(2, -1));
+    assert_eq!(
+        matrix,
+        grid([[0, 0, 4, 0], [0, 0, 0, 0], [0, 0, 0, 0]], false)
+    );
+}
+
+/// 0  1  2  3
+/// 4  5  6  7
+/// 8  9 10 11
+fn counting() -> Matrix<u8> {
+    grid([[0, 1, 2, 3], [4, 5, 6, 7], [8, 9, 10, 11]], false)
+}
+
+#[test]
+fn clamp_clips_each_edge() {
+    let clamped = |x, y, width, height| {
+        counting()
+            .clamp(Position::new(x, y), Dimensions::new(width, height))
+            .copied()
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(clamped(-1, -1, 3, 3), vec![0, 1, 4, 5]);
+    assert_eq!(clamped(2, 1, 5, 5), vec![6, 7, 10, 11]);
+    assert_eq!(clamped(-2, 1, 8, 1), vec![4, 5, 6, 7]);
+    assert_eq!(clamped(4, 0, 2, 2), vec![]);
+    assert_eq!(clamped(-3, -3, 2, 2), vec![]);
+}
+
+#[test]
+fn enumerate_clamp_negative_position() {
+    let enumerated = counting()
+        .enumerate_clamp(Position::new(-1, 1), Dimensions::new(3, 3))
+        .map(|(position, value)| (position.tuple(), *value))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        enumerated,
+        vec![((0, 1), 4), ((1, 1), 5), ((0, 2), 8), ((1, 2), 9)]
+    );
+}
+
+#[test]
+fn view_clips_negative_position() {
+    let view = counting()
+        .view(Position::new(-1, 1), Dimensions::new(3, 5))
+        .to_matrix();
+    assert_eq!(view, grid([[4, 5], [8, 9]], false));
+}
@@ -0,0 +1,112 @@
+use minifb_tile_base::tools::{
+    color::{Color, ParseColorError},
+    named_colors::{DB32, PICO8},
+};
+
+fn parse(s: &str) -> Result<Color, ParseColorError> {
+    s.parse::<Color>()
+}
+
+#[test]
+fn short_hex() {
+    assert_eq!(parse("#f80"), Ok(Color::new(255, 136, 0)));
+}
+
+#[test]
+fn long_hex() {
+    assert_eq!(parse("#1D2b53"), Ok(Color::new(0x1d, 0x2b, 0x53)));
+    assert_eq!(
+        parse("#12345"),
+        Err(ParseColorError::HEX("#12345".to_string()))
+    );
+}
+
+#[test]
+fn rgb_with_spaces() {
+    assert_eq!(parse("  RGB( 1 ,2,  3 ) "), Ok(Color::new(1, 2, 3)));
+}
+
+#[test]
+fn rgb_channel_too_large() {
+    assert_eq!(
+        parse("rgb(0, 256, 0)"),
+        Err(ParseColorError::RGB("rgb(0, 256, 0)".to_string()))
+    );
+}
+
+#[test]
+fn css_name_ignores_case() {
+    assert_eq!(parse("RebeccaPurple"), Ok(Color::new(0x66, 0x33, 0x99)));
+    assert_eq!(
+        parse("notacolor"),
+        Err(ParseColorError::NAME("notacolor".to_string()))
+    );
+}
+
+#[test]
+fn pico8_by_name_and_index() {
+    assert_eq!(parse("pico8:dark-blue"), Ok(PICO8[1]));
+    assert_eq!(parse("pico8:8"), Ok(PICO8[8]));
+}
+
+#[test]
+fn db32_out_of_range() {
+    assert_eq!(parse("db32:31"), Ok(DB32[31]));
+    assert_eq!(
+        parse("db32:32"),
+        Err(ParseColorError::NAME("db32:32".to_string()))
+    );
+}
+
+#[test]
+fn display_round_trip() {
+    [Color::new(0, 0, 0), Color::new(1, 128, 255), PICO8[14]]
+        .into_iter()
+        .for_each(|color| {
+            assert_eq!(color.to_string().len(), 7);
+            assert_eq!(parse(&color.to_string()), Ok(color))
+        });
+}
+
+#[test]
+fn constants_match_css_names() {
+    [
+        ("black", Color::BLACK),
+        ("white", Color::WHITE),
+        ("gray", Color::GRAY),
+        ("red", Color::RED),
+        ("lime", Color::LIME),
+        ("blue", Color::BLUE),
+        ("yellow", Color::YELLOW),
+        ("cyan", Color::CYAN),
+        ("magenta", Color::MAGENTA),
+    ]
+    .into_iter()
+    .for_each(|(name, color)| assert_eq!(parse(name), Ok(color), "{name}"));
+}
+
+#[test]
+fn distance_does_not_overflow() {
+    assert_eq!(Color::BLACK.distance_from(Color::WHITE), 765);
+    assert_eq!(Color::WHITE.distance_from(Color::BLACK), 765);
+    assert_eq!(Color::RED.distance_from(Color::RED), 0);
+}
+
+#[test]
+fn perceptual_distance() {
+    [
+        Color::BLACK,
+        Color::WHITE,
+        Color::RED,
+        Color::new(12, 200, 99),
+    ]
+    .into_iter()
+    .for_each(|color| assert!(color.perceptual_distance(color).abs() < 1e-6, "{color}"));
+
+    let black_to_white = Color::BLACK.perceptual_distance(Color::WHITE);
+    assert!((black_to_white - 1.0).abs() < 1e-3, "{black_to_white}");
+    assert_eq!(
+        black_to_white,
+        Color::WHITE.perceptual_distance(Color::BLACK)
+    );
+}
@@ -0,0 +1,24 @@
+//! Fixtures shared by the integration tests. Each test crate uses only some of them.
+#![allow(dead_code)]
+
+use minifb_tile_base::tools::{dual_trait::Algebra, matrix::Matrix, transform::Dimensions};
+
+/// 1 2 3
+/// 4 5 6
+pub fn sample() -> Matrix<u8> {
+    Matrix {
+        values: vec![1, 2, 3, 4, 5, 6],
+        dimensions: Dimensions::new(3, 2),
+        wrapping: false,
+    }
+}
+
+/// values and dimensions of matrix, to compare against expected ones
+pub fn contents<T: Clone>(matrix: Matrix<T>) -> (Vec<T>, (usize, usize)) {
+    (matrix.values, matrix.dimensions.tuple())
+}
+
+/// matrix with given rows, for writing expected matrices by hand
+pub fn grid<const W: usize, const H: usize>(rows: [[u8; W]; H], wrapping: bool) -> Matrix<u8> {
+    Matrix::from_rows(rows, wrapping).expect("rows are the same length")
+}
@@ -0,0 +1,28 @@
+use minifb_tile_base::tools::{
+    color::Color,
+    convolution::{EdgeMode, Kernel},
+    dual_trait::Algebra,
+    matrix::Matrix,
+    transform::Dimensions,
+};
+
+fn gray(value: u8) -> Matrix<Color> {
+    Matrix::new(Dimensions::splat(3), false).map(|_: &Color| Color::new(value, value, value))
+}
+
+#[test]
+fn gaussian_blur_without_sigma_keeps_colors() {
+    assert_eq!(gray(100).gaussian_blur(0., EdgeMode::CLAMP), gray(100));
+    assert_eq!(gray(100).gaussian_blur(-1., EdgeMode::CLAMP), gray(100));
+}
+
+#[test]
+fn gaussian_blur_keeps_flat_colors() {
+    assert_eq!(gray(100).gaussian_blur(1., EdgeMode::CLAMP), gray(100));
+}
+
+#[test]
+#[should_panic(expected = "kernel needs a weight for each position")]
+fn kernel_rejects_missing_weights() {
+    Kernel::new(vec![1., 2.], Dimensions::splat(3), 0.);
+}
@@ -0,0 +1,49 @@
+use minifb_tile_base::tools::{
+    dual_trait::Algebra,
+    matrix::Matrix,
+    transform::{Dimensions, Position},
+};
+
+type Area = ((isize, isize), (usize, usize));
+
+fn areas(matrix: &Matrix<u8>, previous: &Matrix<u8>) -> Vec<Area> {
+    matrix
+        .diff(previous)
+        .iter()
+        .map(|rect| (rect.position.tuple(), rect.dimensions.tuple()))
+        .collect()
+}
+
+fn blank() -> Matrix<u8> {
+    Matrix::new(Dimensions::new(5, 4), false)
+}
+
+#[test]
+fn unchanged() {
+    assert_eq!(areas(&blank(), &blank()), vec![]);
+}
+
+#[test]
+fn merges_consecutive_rows() {
+    let mut matrix = blank();
+    matrix.set(Position::new(3, 0), 1);
+    matrix.set(Position::new(1, 1), 1);
+    assert_eq!(areas(&matrix, &blank()), vec![((1, 0), (3, 2))]);
+}
+
+#[test]
+fn separates_rows_apart() {
+    let mut matrix = blank();
+    matrix.set(Position::new(0, 0), 1);
+    matrix.set(Position::new(4, 3), 1);
+    assert_eq!(
+        areas(&matrix, &blank()),
+        vec![((0, 0), (1, 1)), ((4, 3), (1, 1))]
+    );
+}
+
+#[test]
+fn different_dimensions() {
+    let smaller = Matrix::new(Dimensions::new(2, 2), false);
+    assert_eq!(areas(&blank(), &smaller), vec![((0, 0), (5, 4))]);
+}
@@ -0,0 +1,20 @@
+use minifb_tile_base::{
+    graphics::palette::Palette,
+    tools::{
+        color::Color, dither::Dither, dual_trait::Algebra, matrix::Matrix, transform::Dimensions,
+    },
+};
+
+#[test]
+fn flat_gray_dithers_to_half_black() {
+    let palette = Palette::from_colors([Color::BLACK, Color::WHITE]);
+    let gray = Matrix::from_fn(Dimensions::splat(4), false, |_| Color::new(128, 128, 128));
+    for dither in [Dither::BAYER2, Dither::BAYER4, Dither::FLOYDSTEINBERG] {
+        let black = gray
+            .dither(&palette, dither)
+            .into_iter()
+            .filter(|index| *index == 1)
+            .count();
+        assert_eq!(black, 8, "{dither:?}");
+    }
+}
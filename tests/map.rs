@@ -0,0 +1,58 @@
+use minifb_tile_base::{
+    graphics::{map::TileMap, tile::Tile},
+    tools::{
+        color::{Color, Pixel},
+        dual_trait::Algebra,
+        matrix::Matrix,
+        transform::{Dimensions, Position},
+    },
+};
+
+/// 2x1 tile, red on the left and transparent on the right
+#[derive(Clone)]
+struct Half(Matrix<Pixel>);
+
+impl Half {
+    fn new() -> Self {
+        Self(Matrix::from_rows([[Some(Color::RED), None]], false).expect("one row"))
+    }
+}
+
+impl Tile for Half {
+    fn get_matrix(&self) -> &Matrix<Pixel> {
+        &self.0
+    }
+
+    fn get_iter(&self) -> impl Iterator<Item = Pixel> {
+        self.0.values.iter().copied()
+    }
+}
+
+/// 2x1 tiles, a tile at (0, 0) and nothing at (1, 0), over a blue buffer
+fn map() -> TileMap<Half> {
+    let mut map = TileMap::new(Dimensions::new(2, 1), false, Dimensions::new(2, 1));
+    map.map.set(Position::new(0, 0), Some(Half::new()));
+    map.buffer.values.fill(Color::BLUE);
+    map
+}
+
+#[test]
+fn draws_over_buffer() {
+    let mut map = map();
+    map.update_buffer();
+    assert_eq!(
+        map.buffer.values,
+        vec![Color::RED, Color::BLUE, Color::BLUE, Color::BLUE]
+    );
+}
+
+#[test]
+fn clears_to_background() {
+    let mut map = map();
+    map.background = Some(Color::BLACK);
+    map.update_buffer();
+    assert_eq!(
+        map.buffer.values,
+        vec![Color::RED, Color::BLACK, Color::BLACK, Color::BLACK]
+    );
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use common::{contents, sample};
+use minifb_tile_base::tools::{
+    dual_trait::Algebra,
+    matrix::Matrix,
+    transform::{Orientation, Rotation},
+};
+
+fn oriented(matrix: &Matrix<u8>, orientation: Orientation) -> (Vec<u8>, (usize, usize)) {
+    (
+        matrix.iter_rotate(orientation).copied().collect(),
+        orientation.dimensions(matrix.dimensions).tuple(),
+    )
+}
+
+fn assert_orientation(orientation: Orientation, values: [u8; 6], dimensions: (usize, usize)) {
+    assert_eq!(
+        oriented(&sample(), orientation),
+        (values.to_vec(), dimensions)
+    );
+
+    let mut matrix = sample();
+    matrix.rotate(orientation);
+    assert_eq!(contents(matrix), (values.to_vec(), dimensions));
+}
+
+#[test]
+fn identity() {
+    assert_orientation(Orientation::IDENTITY, [1, 2, 3, 4, 5, 6], (3, 2));
+}
+
+#[test]
+fn rotate_right() {
+    assert_orientation(Rotation::RIGHT.into(), [4, 1, 5, 2, 6, 3], (2, 3));
+}
+
+#[test]
+fn rotate_180() {
+    assert_orientation(Rotation::DOWN.into(), [6, 5, 4, 3, 2, 1], (3, 2));
+}
+
+#[test]
+fn rotate_left() {
+    assert_orientation(Rotation::LEFT.into(), [3, 6, 2, 5, 1, 4], (2, 3));
+}
+
+#[test]
+fn reflect_horizontal() {
+    assert_orientation(Orientation::REFLECT_HORIZONTAL, [3, 2, 1, 6, 5, 4], (3, 2));
+}
+
+#[test]
+fn reflect_vertical() {
+    assert_orientation(Orientation::REFLECT_VERTICAL, [4, 5, 6, 1, 2, 3], (3, 2));
+}
+
+#[test]
+fn reflect_diagonal() {
+    assert_orientation(Orientation::REFLECT_DIAGONAL, [1, 4, 2, 5, 3, 6], (2, 3));
+}
+
+#[test]
+fn reflect_negative_diagonal() {
+    assert_orientation(
+        Orientation::REFLECT_NEGATIVE_DIAGONAL,
+        [6, 3, 5, 2, 4, 1],
+        (2, 3),
+    );
+}
+
+#[test]
+fn all_orientations_are_distinct() {
+    let all = Orientation::all();
+    all.iter().enumerate().for_each(|(i, a)| {
+        all.iter()
+            .skip(i + 1)
+            .for_each(|b| assert_ne!(oriented(&sample(), *a), oriented(&sample(), *b)))
+    });
+}
+
+#[test]
+fn composition_matches_applying_in_sequence() {
+    Orientation::all().into_iter().for_each(|first| {
+        Orientation::all().into_iter().for_each(|second| {
+            let mut matrix = sample();
+            matrix.rotate(first);
+            matrix.rotate(second);
+            assert_eq!(
+                (matrix.values, matrix.dimensions.tuple()),
+                oriented(&sample(), first.then(second)),
+                "{first:?} then {second:?}"
+            );
+        })
+    });
+}
+
+#[test]
+fn inverse_undoes_orientation() {
+    Orientation::all().into_iter().for_each(|orientation| {
+        assert_eq!(
+            orientation.then(orientation.inverse()),
+            Orientation::IDENTITY
+        );
+        assert_eq!(
+            orientation.inverse().then(orientation),
+            Orientation::IDENTITY
+        );
+
+        let mut matrix = sample();
+        matrix.rotate(orientation);
+        matrix.rotate(orientation.inverse());
+        assert_eq!(
+            (matrix.values, matrix.dimensions.tuple()),
+            oriented(&sample(), Orientation::IDENTITY)
+        );
+    });
+}
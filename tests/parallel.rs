@@ -0,0 +1,82 @@
+//! Fixed results of the functions that run rows in parallel with the rayon feature. Run with and
+//! without the feature to check both paths give the same results.
+use minifb_tile_base::{
+    graphics::{map::TileMap, tile::Tile},
+    tools::{
+        color::{Color, Pixel},
+        convolution::{EdgeMode, Kernel},
+        dual_trait::Algebra,
+        matrix::Matrix,
+        transform::{Dimensions, Position},
+    },
+};
+
+#[derive(Clone)]
+struct Square(Matrix<Pixel>);
+
+impl Tile for Square {
+    fn get_matrix(&self) -> &Matrix<Pixel> {
+        &self.0
+    }
+
+    fn get_iter(&self) -> impl Iterator<Item = Pixel> {
+        self.0.values.iter().copied()
+    }
+}
+
+#[test]
+fn update_buffer() {
+    let (r, w, k) = (Color::RED, Color::WHITE, Color::BLACK);
+    let diagonal = Square(
+        Matrix::from_rows([[Some(r), None], [None, Some(r)]], false)
+            .expect("rows are the same length"),
+    );
+    let full = Square(Matrix::from_fn(Dimensions::splat(2), false, |_| Some(w)));
+    let mut map = TileMap::new(Dimensions::new(3, 2), false, Dimensions::splat(2));
+    map.map.set(Position::new(0, 0), Some(diagonal.clone()));
+    map.map.set(Position::new(1, 0), Some(full.clone()));
+    map.map.set(Position::new(1, 1), Some(diagonal));
+    map.map.set(Position::new(2, 1), Some(full));
+    map.update_buffer();
+    assert_eq!(
+        map.buffer.values,
+        vec![
+            r, k, w, w, k, k, //
+            k, r, w, w, k, k, //
+            k, k, r, k, w, w, //
+            k, k, k, r, w, w,
+        ]
+    );
+}
+
+#[test]
+fn convolve() {
+    let gray = |value: u8| Color::new(value, value, value);
+    let matrix = Matrix::from_rows(
+        [[0, 60, 120, 180], [0, 40, 80, 120]].map(|row| row.map(gray)),
+        false,
+    )
+    .expect("rows are the same length");
+    let neighbours = Kernel::new(vec![0.5, 0., 0.5], Dimensions::new(3, 1), 0.);
+    assert_eq!(
+        matrix.convolve(&neighbours, EdgeMode::CLAMP).values,
+        [30, 60, 120, 150, 20, 40, 80, 100].map(gray).to_vec()
+    );
+}
+
+#[test]
+fn scale2x() {
+    let diagonal = Matrix::from_rows([[1u8, 0, 0], [0, 1, 0], [0, 0, 1]], false)
+        .expect("rows are the same length");
+    assert_eq!(
+        diagonal.scale2x().values,
+        vec![
+            1, 1, 0, 0, 0, 0, //
+            1, 0, 1, 0, 0, 0, //
+            0, 1, 1, 1, 0, 0, //
+            0, 0, 1, 1, 1, 0, //
+            0, 0, 0, 1, 0, 1, //
+            0, 0, 0, 0, 1, 1,
+        ]
+    );
+}
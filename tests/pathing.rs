@@ -0,0 +1,73 @@
+use minifb_tile_base::tools::{
+    dual_trait::Algebra, matrix::Matrix, pathing::UNREACHABLE, region::Connectivity,
+    transform::Position,
+};
+
+const U: u32 = UNREACHABLE;
+
+/// # is a wall, ~ costs 3 to enter and anything else costs 1
+fn terrain(wrapping: bool) -> Matrix<char> {
+    Matrix::from_rows(
+        [
+            ['.', '.', '.', '#', '.'],
+            ['.', '~', '.', '#', '.'],
+            ['.', '#', '.', '#', '.'],
+        ],
+        wrapping,
+    )
+    .expect("rows are the same length")
+}
+
+fn distances(wrapping: bool) -> Matrix<u32> {
+    terrain(wrapping).dijkstra([Position::new(0, 0)], Connectivity::FOUR, |_, c| match c {
+        '#' => None,
+        '~' => Some(3),
+        _ => Some(1),
+    })
+}
+
+#[test]
+fn dijkstra_map() {
+    assert_eq!(
+        distances(false).values,
+        vec![0, 1, 2, U, U, 1, 2, 3, U, U, 2, U, 4, U, U]
+    );
+}
+
+#[test]
+fn dijkstra_map_wraps() {
+    assert_eq!(
+        distances(true).values,
+        vec![0, 1, 2, U, 1, 1, 2, 3, U, 2, 1, U, 3, U, 2]
+    );
+}
+
+/// offsets of a flow field as tuples, to compare
+fn directions(flow: Matrix<Option<Position>>) -> Vec<Option<(isize, isize)>> {
+    flow.into_iter()
+        .map(|offset| offset.map(|o| o.tuple()))
+        .collect()
+}
+
+#[test]
+fn flow_field_leads_downhill() {
+    let flow = directions(distances(false).flow_field(Connectivity::FOUR));
+    // goal, the wall and the area beyond it have nowhere to go
+    [0, 3, 4, 11, 14]
+        .iter()
+        .for_each(|i| assert_eq!(flow[*i], None));
+    assert_eq!(flow[1], Some((-1, 0)));
+    assert_eq!(flow[5], Some((0, -1)));
+    assert_eq!(flow[12], Some((0, -1)));
+}
+
+#[test]
+fn zero_costs_still_flow() {
+    let flat = Matrix::from_rows([[0u32; 3]], false).expect("one row");
+    let distances = flat.dijkstra([Position::new(0, 0)], Connectivity::FOUR, |_, c| Some(*c));
+    assert_eq!(distances.values, vec![0, 1, 2]);
+    assert_eq!(
+        directions(distances.flow_field(Connectivity::FOUR)),
+        vec![None, Some((-1, 0)), Some((-1, 0))]
+    );
+}
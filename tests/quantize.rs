@@ -0,0 +1,52 @@
+use minifb_tile_base::{
+    graphics::palette::Palette,
+    tools::{
+        color::{Color, Pixel},
+        matrix::Matrix,
+        quantize::Quantizer,
+    },
+};
+
+/// red, green and blue stripes with a transparent hole
+fn stripes() -> Matrix<Pixel> {
+    let (r, g, b) = (Some(Color::RED), Some(Color::LIME), Some(Color::BLUE));
+    Matrix::from_rows([[r, g, b], [r, None, b], [r, g, b]], false)
+        .expect("rows are the same length")
+}
+
+#[test]
+fn keeps_colors_that_fit() {
+    [Quantizer::MEDIANCUT, Quantizer::KMEANS(4)]
+        .into_iter()
+        .for_each(|quantizer| {
+            let (palette, indices) = stripes().quantize(3, quantizer);
+            assert_eq!(palette.len(), 4, "{quantizer:?}");
+            assert_eq!(palette.resolve_matrix(&indices), stripes(), "{quantizer:?}");
+        });
+}
+
+#[test]
+fn transparent_pixels_are_index_0() {
+    let (_, indices) = stripes().quantize(2, Quantizer::default());
+    assert_eq!(indices.values[4], 0);
+    assert!(indices
+        .values
+        .iter()
+        .enumerate()
+        .all(|(i, index)| i == 4 || (1..=2).contains(index)));
+}
+
+#[test]
+fn maps_to_nearest_color() {
+    let palette = Palette::from_colors([Color::BLACK, Color::WHITE]);
+    let colors = Matrix::from_rows(
+        [[
+            Color::new(20, 20, 20),
+            Color::new(230, 230, 230),
+            Color::YELLOW,
+        ]],
+        false,
+    )
+    .expect("one row");
+    assert_eq!(colors.map_to_palette(&palette).values, vec![1, 2, 2]);
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::{contents, sample};
+use minifb_tile_base::tools::{
+    dual_trait::Algebra,
+    resize::{Align, Anchor, Margins},
+    transform::{Dimensions, Position, Rect},
+};
+
+#[test]
+fn resize_grows_from_top_left() {
+    let mut matrix = sample();
+    matrix.resize(Dimensions::new(4, 3), Anchor::TOP_LEFT, 0);
+    assert_eq!(
+        contents(matrix),
+        (vec![1, 2, 3, 0, 4, 5, 6, 0, 0, 0, 0, 0], (4, 3))
+    );
+}
+
+#[test]
+fn resize_grows_from_bottom_right() {
+    let mut matrix = sample();
+    matrix.resize(Dimensions::new(4, 3), Anchor::BOTTOM_RIGHT, 9);
+    assert_eq!(
+        contents(matrix),
+        (vec![9, 9, 9, 9, 9, 1, 2, 3, 9, 4, 5, 6], (4, 3))
+    );
+}
+
+#[test]
+fn resize_shrinks_around_center() {
+    let mut matrix = sample();
+    matrix.resize(
+        Dimensions::new(1, 1),
+        Anchor::new(Align::CENTER, Align::END),
+        0,
+    );
+    assert_eq!(contents(matrix), (vec![5], (1, 1)));
+}
+
+#[test]
+fn crop_keeps_rect() {
+    let mut matrix = sample();
+    matrix.crop(Rect::new(Position::new(1, 0), Dimensions::new(2, 2)));
+    assert_eq!(contents(matrix), (vec![2, 3, 5, 6], (2, 2)));
+}
+
+#[test]
+fn crop_clips_to_bounds() {
+    let mut matrix = sample();
+    matrix.crop(Rect::new(Position::new(2, 1), Dimensions::new(5, 5)));
+    assert_eq!(contents(matrix), (vec![6], (1, 1)));
+}
+
+#[test]
+fn pad_adds_margins() {
+    let mut matrix = sample();
+    matrix.pad(Margins::new(1, 0, 0, 1), 0);
+    assert_eq!(
+        contents(matrix),
+        (vec![0, 1, 2, 3, 0, 4, 5, 6, 0, 0, 0, 0], (4, 3))
+    );
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "serde")]
+
+use std::time::Duration;
+
+use minifb_tile_base::tools::{
+    dual_trait::Algebra, matrix::Matrix, timer::Timer, transform::Dimensions,
+};
+
+#[test]
+fn matrix_round_trip() {
+    let matrix = Matrix {
+        values: vec![1u8, 2, 3, 4, 5, 6],
+        dimensions: Dimensions::new(3, 2),
+        wrapping: true,
+    };
+    let json = serde_json::to_string(&matrix).expect("matrix serializes");
+    assert_eq!(serde_json::from_str::<Matrix<u8>>(&json).ok(), Some(matrix));
+}
+
+#[test]
+fn matrix_rejects_wrong_length() {
+    let json = r#"{"values":[1,2,3],"dimensions":{"width":2,"height":2},"wrapping":false}"#;
+    let error = serde_json::from_str::<Matrix<u8>>(json).expect_err("length doesn't match");
+    assert!(error.to_string().contains("matrix has 3 values"), "{error}");
+}
+
+#[test]
+fn timer_round_trip() {
+    let timer = Timer::new(Duration::from_secs(60));
+    let json = serde_json::to_string(&timer).expect("timer serializes");
+    let mut loaded = serde_json::from_str::<Timer>(&json).expect("timer deserializes");
+    assert_eq!(loaded.duration, Duration::from_secs(60));
+    loaded.update();
+    assert!(!loaded.is_finished());
+
+    let finished = r#"{"duration":{"secs":5,"nanos":0},"remaining":{"secs":0,"nanos":0}}"#;
+    let mut loaded = serde_json::from_str::<Timer>(finished).expect("timer deserializes");
+    loaded.update();
+    assert!(loaded.is_finished());
+}
@@ -0,0 +1,86 @@
+use minifb_tile_base::tools::{
+    color::Color,
+    dual_trait::Algebra,
+    matrix::Matrix,
+    scaling::Upscale,
+    transform::{Dimensions, Position, Rect},
+};
+
+const UPSCALES: [Upscale; 5] = [
+    Upscale::NONE,
+    Upscale::NEAREST(3),
+    Upscale::SCALE2X,
+    Upscale::SCALE3X,
+    Upscale::HQ2X,
+];
+
+fn area(upscale: Upscale, x: isize, y: isize, wrapping: bool) -> ((isize, isize), (usize, usize)) {
+    let rect = Rect::new(Position::new(x, y), Dimensions::splat(1));
+    let area = upscale.scale_area(rect, Dimensions::new(4, 3), wrapping);
+    (area.position.tuple(), area.dimensions.tuple())
+}
+
+#[test]
+fn nearest_scales_area() {
+    assert_eq!(area(Upscale::NONE, 1, 2, false), ((1, 2), (1, 1)));
+    assert_eq!(area(Upscale::NEAREST(3), 1, 2, true), ((3, 6), (3, 3)));
+}
+
+#[test]
+fn neighbours_grow_area() {
+    assert_eq!(area(Upscale::SCALE2X, 1, 1, false), ((0, 0), (6, 6)));
+    assert_eq!(area(Upscale::SCALE3X, 0, 0, false), ((0, 0), (6, 6)));
+    assert_eq!(area(Upscale::HQ2X, 3, 1, false), ((4, 0), (4, 6)));
+}
+
+#[test]
+fn wrapping_area_spans_crossed_axis() {
+    assert_eq!(area(Upscale::SCALE2X, 0, 1, true), ((0, 0), (8, 6)));
+    assert_eq!(area(Upscale::HQ2X, 2, 2, true), ((2, 0), (6, 6)));
+}
+
+/// checkerboard-like pattern with diagonal edges for the scalers to smooth
+fn pattern(wrapping: bool) -> Matrix<Color> {
+    Matrix::from_fn(Dimensions::new(5, 4), wrapping, |position| {
+        match (position.x + position.y * 2) % 3 {
+            0 => Color::BLACK,
+            1 => Color::WHITE,
+            _ => Color::RED,
+        }
+    })
+}
+
+#[test]
+fn changes_stay_inside_area() {
+    [false, true].into_iter().for_each(|wrapping| {
+        let before = pattern(wrapping);
+        (0..before.values.len()).for_each(|i| {
+            let mut after = before.clone();
+            after.values[i] = Color::BLUE;
+            let changed = after.diff(&before);
+            UPSCALES.iter().for_each(|upscale| {
+                let scale = |m: &Matrix<Color>| upscale.apply(m).unwrap_or_else(|| m.clone());
+                let (old, new) = (scale(&before), scale(&after));
+                let areas = changed
+                    .iter()
+                    .map(|rect| upscale.scale_area(*rect, before.dimensions, wrapping))
+                    .collect::<Vec<_>>();
+                new.enumerate()
+                    .filter(|(position, color)| old.get(*position) != Some(color))
+                    .for_each(|(position, _)| {
+                        assert!(
+                            areas.iter().any(|area| {
+                                let offset = position.sub(area.position);
+                                offset.x >= 0
+                                    && offset.y >= 0
+                                    && (offset.x as usize) < area.dimensions.width
+                                    && (offset.y as usize) < area.dimensions.height
+                            }),
+                            "{upscale:?} wrapping {wrapping}, change at {i} reached {:?}",
+                            position.tuple()
+                        )
+                    })
+            })
+        })
+    })
+}
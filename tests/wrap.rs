@@ -0,0 +1,80 @@
+mod common;
+
+use common::{grid, sample};
+use minifb_tile_base::tools::{
+    dual_trait::Algebra,
+    matrix::Matrix,
+    transform::{Dimensions, Position},
+};
+
+/// 4x3 of zeros that wraps
+fn blank() -> Matrix<u8> {
+    Matrix::new(Dimensions::new(4, 3), true)
+}
+
+/// blank with sprite overlaid at (x, y), by matrix and by iterator
+fn overlaid(sprite: &Matrix<u8>, x: isize, y: isize) -> Matrix<u8> {
+    let position = Position::new(x, y);
+    let mut matrix = blank();
+    matrix.overlay(sprite, position);
+
+    let mut iterated = blank();
+    iterated.overlay_iter(sprite.values.iter(), position, sprite.dimensions);
+    assert_eq!(iterated, matrix);
+    matrix
+}
+
+#[test]
+fn crosses_right_and_bottom_edges() {
+    assert_eq!(
+        overlaid(&sample(), 2, 2),
+        grid([[6, 0, 4, 5], [0, 0, 0, 0], [3, 0, 1, 2]], true)
+    );
+}
+
+#[test]
+fn negative_position() {
+    assert_eq!(
+        overlaid(&sample(), -1, -1),
+        grid([[5, 6, 0, 4], [0, 0, 0, 0], [2, 3, 0, 1]], true)
+    );
+}
+
+#[test]
+fn far_positions_wrap_around() {
+    assert_eq!(overlaid(&sample(), 6, 5), overlaid(&sample(), 2, 2));
+    assert_eq!(overlaid(&sample(), -5, -4), overlaid(&sample(), -1, -1));
+}
+
+#[test]
+fn larger_sprite_keeps_top_left() {
+    // 1 to 20 in 5 columns and 4 rows
+    let sprite = Matrix::from_fn(Dimensions::new(5, 4), false, |position| {
+        (position.x + position.y * 5 + 1) as u8
+    });
+    assert_eq!(
+        overlaid(&sprite, 1, 1),
+        grid([[14, 11, 12, 13], [4, 1, 2, 3], [9, 6, 7, 8]], true)
+    );
+}
+
+#[test]
+fn transparent_overlay_wraps() {
+    let mut matrix = blank();
+    let sprite = sample().map(|value| (value % 2 == 0).then_some(*value));
+    matrix.transparent_overlay(&sprite, Position::new(3, 2));
+    assert_eq!(
+        matrix,
+        grid([[0, 6, 0, 4], [0, 0, 0, 0], [2, 0, 0, 0]], true)
+    );
+}
+
+#[test]
+fn clamp_wrap_mut_visits_each_value_once() {
+    let mut matrix = grid([[0, 1, 2, 3], [4, 5, 6, 7], [8, 9, 10, 11]], true);
+    let visited = matrix
+        .clamp_wrap_mut(Position::new(3, 2), Dimensions::new(9, 9))
+        .map(|value| *value)
+        .collect::<Vec<_>>();
+    assert_eq!(visited, vec![11, 8, 9, 10, 3, 0, 1, 2, 7, 4, 5, 6]);
+}